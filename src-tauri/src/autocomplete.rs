@@ -0,0 +1,139 @@
+use chrono::Local;
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::content_index::indexed_paths_under;
+use crate::search_cache::cached_paths_under;
+use crate::search_history;
+
+const DEFAULT_SUGGESTION_LIMIT: usize = 10;
+
+// a history entry loses half of its weight every HISTORY_HALF_LIFE_DAYS
+const HISTORY_HALF_LIFE_DAYS: f64 = 14.0;
+const SAME_DIR_BOOST: f64 = 2.0;
+const RELATED_DIR_BOOST: f64 = 1.5;
+// keywords containing (not starting with) the input count less
+const CONTAINS_WEIGHT: f64 = 0.5;
+// one file name counts as much as a 30% fresh history entry
+const FILE_NAME_WEIGHT: f64 = 0.3;
+
+#[derive(Serialize, Debug, Clone)]
+pub struct KeywordSuggestion {
+    keyword: String,
+    score: f64,
+    source: String,
+}
+
+// 1.0 for prefix matches, CONTAINS_WEIGHT for infix matches, None otherwise
+fn match_weight(candidate: &str, partial: &str) -> Option<f64> {
+    let candidate = candidate.to_lowercase();
+    if candidate.starts_with(partial) {
+        Some(1.0)
+    } else if candidate.contains(partial) {
+        Some(CONTAINS_WEIGHT)
+    } else {
+        None
+    }
+}
+
+fn directory_boost(searched_dir: &str, current_dir: &str) -> f64 {
+    if current_dir.is_empty() {
+        return 1.0;
+    }
+    let searched = Path::new(searched_dir);
+    let current = Path::new(current_dir);
+    if searched == current {
+        SAME_DIR_BOOST
+    } else if current.starts_with(searched) || searched.starts_with(current) {
+        RELATED_DIR_BOOST
+    } else {
+        1.0
+    }
+}
+
+fn time_decay(searched_at: i64, now: i64) -> f64 {
+    let age_days = (now - searched_at).max(0) as f64 / (24.0 * 60.0 * 60.0);
+    (-std::f64::consts::LN_2 * age_days / HISTORY_HALF_LIFE_DAYS).exp()
+}
+
+fn add_score(scores: &mut HashMap<String, (f64, &'static str)>, keyword: String, score: f64, source: &'static str) {
+    let entry = scores.entry(keyword).or_insert((0.0, source));
+    entry.0 += score;
+}
+
+// file names known under the current folder (cached search results, content index)
+fn known_file_names(current_dir: &str) -> Vec<String> {
+    if current_dir.is_empty() {
+        return vec![];
+    }
+    let current = Path::new(current_dir);
+
+    let mut paths = cached_paths_under(current);
+    paths.extend(indexed_paths_under(current));
+    paths.sort();
+    paths.dedup();
+
+    paths
+        .into_iter()
+        .filter_map(|file_path| {
+            Path::new(&file_path).file_stem().and_then(|name| name.to_str()).map(|name| name.to_string())
+        })
+        .collect()
+}
+
+/// 검색어 자동완성
+///
+/// 입력 중인 검색어와 현재 폴더를 받아 추천 검색어를 점수순으로 반환
+/// 검색 기록(최근일수록, 같은 폴더일수록 높은 점수)과 현재 폴더의 알려진 파일명을 함께 사용
+///
+/// # Arguments
+///
+/// * `partial` - 입력 중인 검색어
+/// * `current_dir` - 현재 폴더 경로
+/// * `limit` - 최대 개수 (기본 10)
+///
+/// # Returns
+///
+/// * `Vec<KeywordSuggestion>` - 추천 검색어 (점수 내림차순)
+#[tauri::command]
+pub fn autocomplete_keywords(partial: String, current_dir: String, limit: Option<usize>) -> Vec<KeywordSuggestion> {
+    let partial = partial.trim().to_lowercase();
+    let now = Local::now().timestamp();
+    let mut scores: HashMap<String, (f64, &'static str)> = HashMap::new();
+
+    for entry in search_history::all_entries() {
+        if let Some(weight) = match_weight(&entry.keyword, &partial) {
+            let score = weight * time_decay(entry.searched_at, now) * directory_boost(&entry.directory, &current_dir);
+            add_score(&mut scores, entry.keyword, score, "history");
+        }
+    }
+
+    if !partial.is_empty() {
+        for name in known_file_names(&current_dir) {
+            if let Some(weight) = match_weight(&name, &partial) {
+                add_score(&mut scores, name, weight * FILE_NAME_WEIGHT, "file");
+            }
+        }
+    }
+
+    let mut suggestions: Vec<KeywordSuggestion> = scores
+        .into_iter()
+        .filter(|(keyword, _)| keyword.to_lowercase() != partial)
+        .map(|(keyword, (score, source))| KeywordSuggestion {
+            keyword,
+            score,
+            source: source.to_string(),
+        })
+        .collect();
+
+    suggestions.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| a.keyword.cmp(&b.keyword))
+    });
+    suggestions.truncate(limit.unwrap_or(DEFAULT_SUGGESTION_LIMIT));
+    suggestions
+}
@@ -0,0 +1,156 @@
+use once_cell::sync::Lazy;
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::runtime::Runtime;
+use tokio::task::JoinHandle;
+
+// "priority" option of searches and scans
+pub const PRIORITY_BACKGROUND: &str = "1";
+
+const BACKGROUND_WORKER_THREADS: usize = 2;
+
+// background reads per second while the app is idle / while a foreground search runs
+const IDLE_READS_PER_SEC: f64 = 2000.0;
+const BUSY_READS_PER_SEC: f64 = 200.0;
+// reading this many bytes of content costs as much as one metadata read
+const READ_COST_BYTES: u64 = 64 * 1024;
+
+#[cfg(target_os = "linux")]
+const BACKGROUND_NICE: libc::c_int = 10;
+#[cfg(target_os = "linux")]
+const IOPRIO_WHO_PROCESS: libc::c_long = 1;
+#[cfg(target_os = "linux")]
+const IOPRIO_CLASS_IDLE: libc::c_long = 3;
+#[cfg(target_os = "linux")]
+const IOPRIO_CLASS_SHIFT: libc::c_long = 13;
+
+// searches / scans running at normal priority
+static FOREGROUND_JOBS: AtomicUsize = AtomicUsize::new(0);
+
+// earliest time the next background read may start
+static NEXT_READ_SLOT: Lazy<Mutex<Instant>> = Lazy::new(|| Mutex::new(Instant::now()));
+
+// every thread of this runtime runs with lowered CPU and I/O priority,
+// so whatever a background job spawns stays in the background too
+static BACKGROUND_RUNTIME: Lazy<Runtime> = Lazy::new(|| {
+    tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(BACKGROUND_WORKER_THREADS)
+        .thread_name("background-io")
+        .on_thread_start(lower_current_thread_priority)
+        .enable_all()
+        .build()
+        .expect("Failed to build background runtime")
+});
+
+#[cfg(target_os = "linux")]
+fn lower_current_thread_priority() {
+    unsafe {
+        // on Linux nice and ioprio apply per thread when given a thread id
+        let tid = libc::syscall(libc::SYS_gettid);
+        if libc::setpriority(libc::PRIO_PROCESS, tid as libc::id_t, BACKGROUND_NICE) != 0 {
+            println!("Failed to lower CPU priority: {}", std::io::Error::last_os_error());
+        }
+        let ioprio = IOPRIO_CLASS_IDLE << IOPRIO_CLASS_SHIFT;
+        if libc::syscall(libc::SYS_ioprio_set, IOPRIO_WHO_PROCESS, tid, ioprio) != 0 {
+            println!("Failed to lower I/O priority: {}", std::io::Error::last_os_error());
+        }
+    }
+}
+
+#[cfg(windows)]
+fn lower_current_thread_priority() {
+    use windows::Win32::System::Threading::{GetCurrentThread, SetThreadPriority, THREAD_MODE_BACKGROUND_BEGIN};
+
+    // background mode lowers both CPU and I/O priority of the thread
+    if let Err(e) = unsafe { SetThreadPriority(GetCurrentThread(), THREAD_MODE_BACKGROUND_BEGIN) } {
+        println!("Failed to enter background mode: {:?}", e);
+    }
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+fn lower_current_thread_priority() {
+    // no per-thread priority here; the read throttle still applies
+}
+
+// marks a normal-priority job as running while alive; background jobs slow down meanwhile
+pub struct ForegroundJob;
+
+impl Drop for ForegroundJob {
+    fn drop(&mut self) {
+        FOREGROUND_JOBS.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+pub fn foreground_job() -> ForegroundJob {
+    FOREGROUND_JOBS.fetch_add(1, Ordering::SeqCst);
+    ForegroundJob
+}
+
+fn is_app_busy() -> bool {
+    FOREGROUND_JOBS.load(Ordering::SeqCst) > 0
+}
+
+// cost of reading a file's content, in metadata reads
+pub fn read_cost(size: u64) -> u32 {
+    1 + (size / READ_COST_BYTES).min(u32::MAX as u64 - 1) as u32
+}
+
+// reserve `cost` reads and return how long to wait before doing them
+fn reserve_reads(cost: u32) -> Duration {
+    let rate = if is_app_busy() { BUSY_READS_PER_SEC } else { IDLE_READS_PER_SEC };
+    let mut next_slot = match NEXT_READ_SLOT.lock() {
+        Ok(next_slot) => next_slot,
+        Err(_) => return Duration::ZERO,
+    };
+    let now = Instant::now();
+    if *next_slot < now {
+        *next_slot = now;
+    }
+    let wait = *next_slot - now;
+    *next_slot += Duration::from_secs_f64(cost as f64 / rate);
+    wait
+}
+
+// rate limit for background reads (all background jobs share one budget)
+pub async fn throttle(cost: u32) {
+    let wait = reserve_reads(cost);
+    if wait.is_zero() {
+        tokio::task::yield_now().await;
+    } else {
+        tokio::time::sleep(wait).await;
+    }
+}
+
+pub fn throttle_blocking(cost: u32) {
+    let wait = reserve_reads(cost);
+    if !wait.is_zero() {
+        std::thread::sleep(wait);
+    }
+}
+
+// run on the background runtime, or on the current one at normal priority
+pub fn spawn<F>(background: bool, future: F) -> JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    if background {
+        BACKGROUND_RUNTIME.spawn(future)
+    } else {
+        tokio::spawn(future)
+    }
+}
+
+pub fn spawn_blocking<F, R>(background: bool, job: F) -> JoinHandle<R>
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    if background {
+        BACKGROUND_RUNTIME.spawn_blocking(job)
+    } else {
+        tokio::task::spawn_blocking(job)
+    }
+}
@@ -0,0 +1,293 @@
+// Headless search : the same engine, options and fuzzy thresholds as the app, for scripts and CI
+//
+//   search_cli [OPTIONS] <QUERY> <ROOT>...
+//
+// Data files are read relative to the app folder like the app does ("../backend_properties/...",
+// "../src/properties/..."), so run it from src-tauri or pass --app-dir.
+
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use app_lib::result_sink::{ResultSink, SinkFuture};
+use app_lib::sch_adv_properties_slot::{saved_slot_settings, SearchSettings};
+use app_lib::search::{FileItem, SearchOptions};
+use app_lib::search_engine::search_into;
+
+const USAGE: &str = "\
+Usage: search_cli [OPTIONS] <QUERY> <ROOT>...
+
+Options:
+  --slot <N>          use the search options saved in slot N
+  --method <CODE>     search method (0 default, 1 regex, 2 Damerau-Levenshtein,
+                      3 Jaccard, 4 byte pattern, 5 content index)
+  --options <JSON>    SearchOptions fields to override, e.g. '{\"searchScope\":\"1\"}'
+  --format <FORMAT>   text (one path per line, default) or json (one result per line)
+  --output <FILE>     write results to FILE instead of stdout
+  --app-dir <DIR>     folder the app runs from (src-tauri); default: current folder
+  -h, --help          show this help
+
+Exit status: 0 if something matched, 1 if nothing did, 2 on errors.";
+
+#[derive(PartialEq)]
+enum Format {
+    Text,
+    Json,
+}
+
+struct CliArgs {
+    query: String,
+    roots: Vec<PathBuf>,
+    slot: Option<u32>,
+    method: Option<String>,
+    options: Option<String>,
+    format: Format,
+    output: Option<PathBuf>,
+    app_dir: Option<PathBuf>,
+}
+
+fn parse_args(args: impl Iterator<Item = String>) -> Result<Option<CliArgs>, String> {
+    let mut positional = Vec::new();
+    let mut cli = CliArgs {
+        query: String::new(),
+        roots: Vec::new(),
+        slot: None,
+        method: None,
+        options: None,
+        format: Format::Text,
+        output: None,
+        app_dir: None,
+    };
+
+    let mut args = args.peekable();
+    while let Some(arg) = args.next() {
+        // "--name=value" and "--name value" both work
+        let (name, inline_value) = match arg.split_once('=') {
+            Some((name, value)) if name.starts_with("--") => (name.to_string(), Some(value.to_string())),
+            _ => (arg.clone(), None),
+        };
+        let mut value = |name: &str| {
+            inline_value
+                .clone()
+                .or_else(|| args.next())
+                .ok_or_else(|| format!("{} needs a value", name))
+        };
+
+        match name.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "--slot" => {
+                let slot = value("--slot")?;
+                cli.slot = Some(slot.parse().map_err(|_| format!("Invalid slot number: {}", slot))?);
+            }
+            "--method" => cli.method = Some(value("--method")?),
+            "--options" => cli.options = Some(value("--options")?),
+            "--format" => {
+                cli.format = match value("--format")?.as_str() {
+                    "text" => Format::Text,
+                    "json" => Format::Json,
+                    other => return Err(format!("Unknown format: {}", other)),
+                }
+            }
+            "--output" => cli.output = Some(PathBuf::from(value("--output")?)),
+            "--app-dir" => cli.app_dir = Some(PathBuf::from(value("--app-dir")?)),
+            // everything after "--" is positional (queries starting with '-')
+            "--" => positional.extend(args.by_ref()),
+            _ if arg.starts_with("--") => return Err(format!("Unknown option: {}", arg)),
+            _ => positional.push(arg),
+        }
+    }
+
+    let mut positional = positional.into_iter();
+    cli.query = positional.next().ok_or("Missing query")?;
+    cli.roots = positional.map(PathBuf::from).collect();
+    if cli.roots.is_empty() {
+        return Err("Missing root folder".to_string());
+    }
+    Ok(Some(cli))
+}
+
+// slot (or default) settings, then --options, then --method
+fn build_options(cli: &CliArgs) -> Result<SearchOptions, String> {
+    let settings = match cli.slot {
+        Some(slot) => saved_slot_settings(slot)?,
+        None => SearchSettings::default(),
+    };
+    let mut options = serde_json::to_value(settings).map_err(|e| e.to_string())?;
+
+    if let Some(overrides) = cli.options.as_deref() {
+        let overrides: serde_json::Value =
+            serde_json::from_str(overrides).map_err(|e| format!("Invalid --options JSON: {}", e))?;
+        let overrides = overrides.as_object().ok_or("--options must be a JSON object")?;
+        for (key, value) in overrides {
+            options[key] = value.clone();
+        }
+    }
+    if let Some(method) = cli.method.as_deref() {
+        options["customSchMethod"] = method.into();
+    }
+
+    serde_json::from_value(options).map_err(|e| format!("Invalid search options: {}", e))
+}
+
+// the engine logs with println! : move that to stderr and keep the real stdout for results
+#[cfg(unix)]
+fn take_stdout_for_results() -> Box<dyn Write + Send> {
+    use std::os::unix::io::FromRawFd;
+    unsafe {
+        let results_fd = libc::dup(libc::STDOUT_FILENO);
+        if results_fd < 0 || libc::dup2(libc::STDERR_FILENO, libc::STDOUT_FILENO) < 0 {
+            return Box::new(io::stdout());
+        }
+        Box::new(File::from_raw_fd(results_fd))
+    }
+}
+
+#[cfg(not(unix))]
+fn take_stdout_for_results() -> Box<dyn Write + Send> {
+    // engine logs stay on stdout here; use --output for clean results
+    Box::new(io::stdout())
+}
+
+fn write_result(out: &mut dyn Write, format: &Format, file_item: &FileItem) -> io::Result<()> {
+    match format {
+        Format::Text => writeln!(out, "{}", file_item.file_path),
+        Format::Json => writeln!(out, "{}", serde_json::to_string(file_item).map_err(|e| io::Error::new(io::ErrorKind::Other, e))?),
+    }
+}
+
+struct OutputState {
+    out: Box<dyn Write + Send>,
+    written: HashSet<String>,
+    write_error: Option<io::Error>,
+}
+
+// writes every match as soon as it is found (once, even if roots overlap)
+struct OutputSink {
+    format: Format,
+    state: Mutex<OutputState>,
+}
+
+impl OutputSink {
+    fn new(out: Box<dyn Write + Send>, format: Format) -> Self {
+        OutputSink {
+            format,
+            state: Mutex::new(OutputState { out, written: HashSet::new(), write_error: None }),
+        }
+    }
+
+    fn matched(&self) -> usize {
+        self.state.lock().map(|state| state.written.len()).unwrap_or(0)
+    }
+
+    fn take_write_error(&self) -> Option<io::Error> {
+        self.state.lock().ok().and_then(|mut state| state.write_error.take())
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        match self.state.lock() {
+            Ok(mut state) => state.out.flush(),
+            Err(_) => Err(io::Error::new(io::ErrorKind::Other, "output lock poisoned")),
+        }
+    }
+}
+
+impl ResultSink for OutputSink {
+    fn send(&self, file_item: FileItem) -> SinkFuture<'_> {
+        Box::pin(async move {
+            let mut state = self.state.lock().map_err(|e| e.to_string())?;
+            if !state.written.insert(file_item.file_path.clone()) {
+                return Ok(());
+            }
+            if let Err(e) = write_result(state.out.as_mut(), &self.format, &file_item) {
+                let message = e.to_string();
+                state.write_error = Some(e);
+                // stops the search
+                return Err(message);
+            }
+            Ok(())
+        })
+    }
+}
+
+// exit status, see USAGE
+async fn run() -> i32 {
+    let mut cli = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(cli)) => cli,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return 0;
+        }
+        Err(e) => {
+            eprintln!("search_cli: {}\n\n{}", e, USAGE);
+            return 2;
+        }
+    };
+
+    if let Some(app_dir) = cli.app_dir.clone() {
+        // roots and --output stay relative to where the command was run
+        if let Ok(caller_dir) = std::env::current_dir() {
+            cli.roots = cli.roots.iter().map(|root| caller_dir.join(root)).collect();
+            cli.output = cli.output.map(|output| caller_dir.join(output));
+        }
+        if let Err(e) = std::env::set_current_dir(&app_dir) {
+            eprintln!("search_cli: cannot use app folder {:?}: {}", app_dir, e);
+            return 2;
+        }
+    }
+
+    let options = match build_options(&cli) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("search_cli: {}", e);
+            return 2;
+        }
+    };
+
+    let out: Box<dyn Write + Send> = match cli.output.as_ref() {
+        Some(path) => match File::create(path) {
+            Ok(file) => Box::new(io::BufWriter::new(file)),
+            Err(e) => {
+                eprintln!("search_cli: cannot create {:?}: {}", path, e);
+                return 2;
+            }
+        },
+        None => take_stdout_for_results(),
+    };
+    let output = Arc::new(OutputSink::new(out, cli.format));
+    let sink: Arc<dyn ResultSink> = output.clone();
+
+    // straight to the engine : the app's search cache is neither read nor written
+    let mut failed = false;
+    for root in cli.roots.iter() {
+        if let Err(e) = search_into(root, &cli.query, &options, Arc::clone(&sink)).await {
+            if let Some(write_error) = output.take_write_error() {
+                eprintln!("search_cli: failed to write results: {}", write_error);
+                return 2;
+            }
+            eprintln!("search_cli: {:?}: {}", root, e);
+            failed = true;
+        }
+    }
+    if let Err(e) = output.flush() {
+        eprintln!("search_cli: failed to write results: {}", e);
+        return 2;
+    }
+
+    let matched = output.matched();
+    eprintln!("search_cli: {} result(s)", matched);
+    if failed {
+        2
+    } else if matched == 0 {
+        1
+    } else {
+        0
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    // run() has dropped (and flushed) everything by now
+    std::process::exit(run().await);
+}
@@ -0,0 +1,121 @@
+use std::path::Path;
+use tokio::fs::File;
+use tokio::io::AsyncReadExt;
+
+const SCAN_CHUNK_SIZE: usize = 1024 * 1024;
+// offsets reported per file; the file still matches past this
+pub const BYTE_OFFSET_LIMIT: usize = 1000;
+
+// "89 50 4E 47 ?? 0A" : bytes in hex, ?? matches any byte
+#[derive(Debug, Clone)]
+pub struct BytePattern {
+    bytes: Vec<Option<u8>>,
+    // first fixed byte, used to skip ahead quickly
+    anchor: Option<(usize, u8)>,
+}
+
+impl BytePattern {
+    // spaces are optional : "89504E47??0A" works too
+    pub fn parse(pattern: &str) -> Result<Self, String> {
+        let digits: Vec<char> = pattern.chars().filter(|c| !c.is_whitespace()).collect();
+        if digits.is_empty() {
+            return Err("Empty byte pattern".to_string());
+        }
+        if digits.len() % 2 != 0 {
+            return Err(format!("Byte pattern has an odd number of hex digits: {}", pattern));
+        }
+
+        let bytes = digits
+            .chunks(2)
+            .map(|pair| match pair {
+                ['?', '?'] => Ok(None),
+                [high, low] => {
+                    let hex: String = [*high, *low].iter().collect();
+                    u8::from_str_radix(&hex, 16)
+                        .map(Some)
+                        .map_err(|_| format!("Invalid byte in pattern: {}", hex))
+                }
+                _ => unreachable!(),
+            })
+            .collect::<Result<Vec<Option<u8>>, String>>()?;
+
+        let anchor = bytes.iter().enumerate().find_map(|(i, byte)| byte.map(|byte| (i, byte)));
+        if anchor.is_none() {
+            return Err("Byte pattern needs at least one fixed byte".to_string());
+        }
+        Ok(BytePattern { bytes, anchor })
+    }
+
+    fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    fn matches_at(&self, data: &[u8], start: usize) -> bool {
+        self.bytes
+            .iter()
+            .zip(&data[start..start + self.bytes.len()])
+            .all(|(expected, actual)| expected.map_or(true, |expected| expected == *actual))
+    }
+
+    // start positions of every match inside `data`
+    fn find_all(&self, data: &[u8]) -> Vec<usize> {
+        let mut found = Vec::new();
+        if data.len() < self.bytes.len() {
+            return found;
+        }
+        let (anchor_index, anchor_byte) = self.anchor.unwrap_or((0, 0));
+        let last_start = data.len() - self.bytes.len();
+
+        let mut start = 0;
+        while start <= last_start {
+            let anchor_at = data[start + anchor_index..=last_start + anchor_index]
+                .iter()
+                .position(|byte| *byte == anchor_byte);
+            match anchor_at {
+                Some(skip) => {
+                    let candidate = start + skip;
+                    if self.matches_at(data, candidate) {
+                        found.push(candidate);
+                    }
+                    start = candidate + 1;
+                }
+                None => break,
+            }
+        }
+        found
+    }
+}
+
+// offsets of the pattern in the file, read in chunks so any file size works
+pub async fn scan_file(path: &Path, pattern: &BytePattern) -> std::io::Result<Vec<u64>> {
+    let mut file = File::open(path).await?;
+    let mut offsets = Vec::new();
+
+    // the last len-1 bytes of the previous chunk, so matches across chunk borders are found once
+    let overlap = pattern.len() - 1;
+    let mut buffer: Vec<u8> = Vec::with_capacity(SCAN_CHUNK_SIZE + overlap);
+    let mut buffer_offset: u64 = 0;
+    let mut chunk = vec![0u8; SCAN_CHUNK_SIZE];
+
+    loop {
+        let read = file.read(&mut chunk).await?;
+        if read == 0 {
+            break;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+
+        for start in pattern.find_all(&buffer) {
+            offsets.push(buffer_offset + start as u64);
+            if offsets.len() >= BYTE_OFFSET_LIMIT {
+                return Ok(offsets);
+            }
+        }
+
+        let keep = overlap.min(buffer.len());
+        let drop = buffer.len() - keep;
+        buffer.drain(..drop);
+        buffer_offset += drop as u64;
+    }
+
+    Ok(offsets)
+}
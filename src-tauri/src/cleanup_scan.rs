@@ -0,0 +1,220 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::background_io::{self, PRIORITY_BACKGROUND};
+
+// OS error code returned when resolving a symlink runs into a loop
+#[cfg(unix)]
+pub(crate) const SYMLINK_LOOP_OS_ERROR: i32 = libc::ELOOP;
+#[cfg(windows)]
+pub(crate) const SYMLINK_LOOP_OS_ERROR: i32 = 1921; // ERROR_CANT_RESOLVE_FILENAME
+
+// max hops when following a symlink chain by hand
+const SYMLINK_CHAIN_LIMIT: usize = 40;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CleanupOptions {
+    #[serde(rename = "findEmptyFiles")]
+    find_empty_files: bool,
+    #[serde(rename = "findEmptyDirs")]
+    find_empty_dirs: bool,
+    // true : folders holding only other empty folders are also empty
+    #[serde(rename = "emptyDirRecursive")]
+    empty_dir_recursive: bool,
+    #[serde(rename = "findBrokenSymlinks")]
+    find_broken_symlinks: bool,
+    #[serde(rename = "findSymlinkLoops")]
+    find_symlink_loops: bool,
+    // "1" : background (lower CPU / IO priority, rate-limited reads)
+    #[serde(rename = "priority", default)]
+    priority: String,
+}
+
+impl Default for CleanupOptions {
+    fn default() -> Self {
+        CleanupOptions {
+            find_empty_files: true,
+            find_empty_dirs: true,
+            empty_dir_recursive: true,
+            find_broken_symlinks: true,
+            find_symlink_loops: true,
+            priority: "0".to_string(),
+        }
+    }
+}
+
+// Every list can be passed to front_utils::move_files_to_trash as it is.
+// Empty folders are reported at the topmost level only, so no path in
+// `paths` lies under another one.
+#[derive(Serialize, Debug, Default)]
+pub struct CleanupScanResult {
+    empty_files: Vec<String>,
+    empty_dirs: Vec<String>,
+    broken_symlinks: Vec<String>,
+    symlink_loops: Vec<String>,
+    paths: Vec<String>,
+}
+
+enum SymlinkState {
+    Valid,
+    Broken,
+    Loop,
+}
+
+fn path_to_string(path: &Path) -> String {
+    path.to_string_lossy().to_string()
+}
+
+// follow the link chain by hand : used when the OS error does not tell a loop apart
+fn has_symlink_loop(path: &Path) -> bool {
+    let mut visited = std::collections::HashSet::new();
+    let mut current = path.to_path_buf();
+
+    for _ in 0..SYMLINK_CHAIN_LIMIT {
+        if !visited.insert(current.clone()) {
+            return true;
+        }
+        let target = match fs::read_link(&current) {
+            Ok(target) => target,
+            Err(_) => return false,
+        };
+        current = match current.parent() {
+            Some(parent) if target.is_relative() => parent.join(target),
+            _ => target,
+        };
+        if !current.is_symlink() {
+            return false;
+        }
+    }
+
+    true
+}
+
+fn check_symlink(path: &Path) -> SymlinkState {
+    match fs::metadata(path) {
+        Ok(_) => SymlinkState::Valid,
+        Err(e) => {
+            if e.raw_os_error() == Some(SYMLINK_LOOP_OS_ERROR) || has_symlink_loop(path) {
+                SymlinkState::Loop
+            } else {
+                SymlinkState::Broken
+            }
+        }
+    }
+}
+
+// returns true if `dir` counts as empty under the given options
+fn scan_dir(dir: &Path, is_root: bool, options: &CleanupOptions, result: &mut CleanupScanResult) -> bool {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            println!("Skipping directory due to access error: {}", e);
+            return false;
+        }
+    };
+
+    let mut is_empty = true;
+    let mut empty_children: Vec<PathBuf> = Vec::new();
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+
+        if options.priority == PRIORITY_BACKGROUND {
+            background_io::throttle_blocking(1);
+        }
+
+        // symlink_metadata : never follow links while scanning
+        let metadata = match fs::symlink_metadata(&path) {
+            Ok(meta) => meta,
+            Err(e) => {
+                println!("Skipping file due to access error: {}", e);
+                is_empty = false;
+                continue;
+            }
+        };
+
+        if metadata.file_type().is_symlink() {
+            is_empty = false;
+            match check_symlink(&path) {
+                SymlinkState::Broken if options.find_broken_symlinks => {
+                    result.broken_symlinks.push(path_to_string(&path));
+                }
+                SymlinkState::Loop if options.find_symlink_loops => {
+                    result.symlink_loops.push(path_to_string(&path));
+                }
+                _ => {}
+            }
+        } else if metadata.is_dir() {
+            let child_empty = scan_dir(&path, false, options, result);
+            if child_empty && options.empty_dir_recursive {
+                empty_children.push(path);
+            } else {
+                if child_empty && options.find_empty_dirs {
+                    result.empty_dirs.push(path_to_string(&path));
+                }
+                is_empty = false;
+            }
+        } else {
+            is_empty = false;
+            if metadata.len() == 0 && options.find_empty_files {
+                result.empty_files.push(path_to_string(&path));
+            }
+        }
+    }
+
+    // report only the topmost empty folders; the root itself is never reported
+    if (!is_empty || is_root) && options.find_empty_dirs {
+        result.empty_dirs.extend(empty_children.iter().map(|p| path_to_string(p)));
+    }
+
+    is_empty
+}
+
+pub fn run_cleanup_scan(dir: &Path, options: &CleanupOptions) -> CleanupScanResult {
+    let mut result = CleanupScanResult::default();
+    scan_dir(dir, true, options, &mut result);
+
+    result.empty_files.sort();
+    result.empty_dirs.sort();
+    result.broken_symlinks.sort();
+    result.symlink_loops.sort();
+
+    result.paths = result.empty_files.iter()
+        .chain(result.empty_dirs.iter())
+        .chain(result.broken_symlinks.iter())
+        .chain(result.symlink_loops.iter())
+        .cloned()
+        .collect();
+
+    result
+}
+
+/// 정리 대상 탐색
+///
+/// 0바이트 파일, 빈 폴더, 깨진 심볼릭 링크, 순환 심볼릭 링크를 탐색
+///
+/// # Arguments
+///
+/// * `directory` - 탐색시작 폴더 경로
+/// * `options` - 탐색할 항목 선택 (없으면 전부 탐색)
+///
+/// # Returns
+///
+/// * `Result<CleanupScanResult, String>` - 항목별 경로 목록과 move_files_to_trash에 넘길 전체 목록
+#[tauri::command]
+pub async fn cleanup_scan(directory: String, options: Option<CleanupOptions>) -> Result<CleanupScanResult, String> {
+    let dir_path = PathBuf::from(directory);
+
+    if !dir_path.is_dir() {
+        return Err(format!("Directory does not exist: {:?}", dir_path));
+    }
+
+    let options = options.unwrap_or_default();
+
+    let background = options.priority == PRIORITY_BACKGROUND;
+    let _foreground_job = (!background).then(background_io::foreground_job);
+    background_io::spawn_blocking(background, move || run_cleanup_scan(&dir_path, &options))
+        .await
+        .map_err(|e| format!("Task failed: {:?}", e))
+}
@@ -1,11 +1,11 @@
 
+use std::collections::BTreeMap;
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
 use trash;
 use std::process::Command;
 use serde::{Serialize, Deserialize};
-use std::collections::HashMap;
 
 use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -21,6 +21,12 @@ use sysinfo::Disks;
 
 use regex::Regex;
 
+use crate::file_xattr::read_xattrs;
+use crate::hard_links::{link_info, LinkInfo};
+use crate::media_tags::{read_media_tags, MediaTags};
+use crate::search_history;
+use crate::smart_folder;
+
 
 // struct for Drive Infos
 #[derive(serde::Serialize)]
@@ -39,6 +45,15 @@ pub struct FileMetadata {
     file_size: u64,
     last_modified: u64,
     file_type: String,
+    // 사진 / 음악 파일의 태그 (EXIF, ID3, Vorbis, MP4)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    media: Option<MediaTags>,
+    // 확장 속성 (user.xdg.tags 등, unix 전용)
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    xattrs: BTreeMap<String, String>,
+    // inode / 장치 / 링크 수 (unix 전용)
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    links: Option<LinkInfo>,
 }
 
 #[derive(Debug, serde::Serialize)]
@@ -73,6 +88,7 @@ pub fn get_current_dir() -> Result<String, String> {
 /// 파일리스트 탐색
 ///
 /// 폴더 경로를 받아 해당 경로의 모든 폴더와 파일 리스트 반환
+/// "smart://<id>" 경로는 스마트 폴더의 검색을 다시 실행하여 결과 반환
 ///
 /// # Arguments
 ///
@@ -82,7 +98,14 @@ pub fn get_current_dir() -> Result<String, String> {
 ///
 /// * `Result<Vec<String>, String>` - 디렉토리경로 문자열
 #[tauri::command]
-pub fn list_files_in_directory(path: String) -> Result<Vec<String>, String> {
+pub async fn list_files_in_directory(path: String) -> Result<Vec<String>, String> {
+    if smart_folder::is_smart_folder_path(&path) {
+        return smart_folder::list_smart_folder(&path).await;
+    }
+    read_directory_entries(path)
+}
+
+fn read_directory_entries(path: String) -> Result<Vec<String>, String> {
     match fs::read_dir(path) {
         Ok(entries) => {
             let mut files = Vec::new();
@@ -102,6 +125,9 @@ pub fn list_files_in_directory(path: String) -> Result<Vec<String>, String> {
 /// 파일 메타데이터
 ///
 /// 파일의 메타데이터(최종 수정일 / 확장자 / 크기) 반환
+/// 사진 / 음악 파일이면 촬영일, 카메라 모델, GPS 여부, 해상도, 아티스트, 앨범, 제목, 재생 시간도 함께 반환
+/// 확장 속성(xattr)이 있으면 이름과 값도 함께 반환
+/// unix에서는 inode, 장치 번호, 하드 링크 수도 함께 반환
 ///
 /// # Arguments
 ///
@@ -131,10 +157,13 @@ pub fn get_file_metadata(file_path: String) -> Result<FileMetadata, String> {
 
     // FileMetadata 구조체로 반환
     let file_metadata = FileMetadata {
-        file_name: file_path,
+        file_name: file_path.clone(),
         file_size: metadata.len(),
         last_modified,
         file_type,
+        media: if metadata.is_file() { read_media_tags(Path::new(&file_path)) } else { None },
+        xattrs: read_xattrs(Path::new(&file_path)),
+        links: link_info(&metadata),
     };
 
     Ok(file_metadata)
@@ -289,6 +318,10 @@ pub fn move_to_trash(del_path: String) -> Result<(), String> {
 /// * `Result<bool, String> - true/false 값
 #[tauri::command]
 pub fn is_directory(path: String) -> Result<bool, String> {
+    if smart_folder::is_smart_folder_path(&path) {
+        return Ok(smart_folder::smart_folder_exists(&path));
+    }
+
     let metadata = fs::metadata(&path);
     
     match metadata {
@@ -406,6 +439,9 @@ pub fn save_util_buttons(buttons: Vec<String>) -> Result<(), String> {
 /// bool
 #[tauri::command]
 pub fn path_exists(dir_path: String) -> bool {
+    if smart_folder::is_smart_folder_path(&dir_path) {
+        return smart_folder::smart_folder_exists(&dir_path);
+    }
     Path::new(&dir_path).exists()
 }
 
@@ -445,7 +481,7 @@ pub fn create_new_item(is_folder: bool, base_path: String) -> Result<String, Str
 
 /// 고유한 이름을 생성하는 함수 (중복 이름 검사 및 넘버링 처리)
 fn generate_unique_name(base_path: &Path, base_name: &str, is_folder: bool) -> Result<String, String> {
-    let existing_items = list_files_in_directory(base_path.to_string_lossy().to_string())?;
+    let existing_items = read_directory_entries(base_path.to_string_lossy().to_string())?;
 
     // 정규 표현식을 통해 "new", "new(1)", "new(2)" 등의 이름을 탐지
     let regex = Regex::new(r"^new(?:\((\d+)\))?(?:\.txt)?$").unwrap();  // 파일 확장자 고려
@@ -516,61 +552,17 @@ pub fn rename_file_or_directory(old_path: String, new_path: String) -> Result<()
 
 
 
-#[derive(Serialize, Deserialize, Debug)]
-struct LogEntry {
-    keyword: String,  // 로그에서 추출할 키워드 필드
-    // 나머지 로그 구조가 필요할 경우 여기에 추가 가능
-}
-
 // Tauri 명령으로 사용할 키워드 리스트 구조체
 #[derive(Serialize, Debug)]
 pub struct KeywordList {
     keywords: Vec<String>,
 }
 
-// 로그 파일에서 키워드 추출 및 정렬
+// 검색 기록에서 키워드 추출 및 정렬
 fn generate_keyword_list() -> Vec<String> {
     println!("Starting to generate keyword list...");
 
-    // log/ 폴더 내의 모든 로그 파일 읽기
-    let log_files = match fs::read_dir("../logs") {
-        Ok(files) => files,
-        Err(e) => {
-            eprintln!("Error reading log directory: {}", e);
-            return vec![];
-        }
-    };
-
-    let mut keyword_count: HashMap<String, usize> = HashMap::new();
-    let mut processed_files = 0;
-
-    // 각 로그 파일에서 키워드 추출 및 빈도 계산
-    for entry in log_files {
-        if let Ok(file) = entry {
-            let path = file.path();
-            // println!("Processing file: {:?}", path);
-
-            let content = match fs::read_to_string(&path) {
-                Ok(content) => content,
-                Err(e) => {
-                    // eprintln!("Failed to read file {:?}: {}", path, e);
-                    continue;
-                }
-            };
-
-            match serde_json::from_str::<LogEntry>(&content) {
-                Ok(log) => {
-                    // println!("Extracted keyword: {}", log.keyword);
-                    *keyword_count.entry(log.keyword.clone()).or_insert(0) += 1;
-                }
-                Err(e) => eprintln!("Failed to parse JSON in {:?}: {}", path, e),
-            }
-
-            processed_files += 1;
-        }
-    }
-
-    // println!("Processed {} files.", processed_files);
+    let keyword_count = search_history::keyword_counts();
 
     // 빈도순 정렬 후 알파벳순 정렬
     let mut sorted_keywords: Vec<_> = keyword_count.into_iter().collect();
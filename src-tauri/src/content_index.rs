@@ -0,0 +1,594 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
+
+use crate::query_terms;
+use crate::result_sink::ResultSink;
+use crate::search::{should_filter_file_by_metadata, FileItem, SearchOptions};
+use crate::search_engine::read_text_content;
+
+const CONTENT_INDEX_PATH: &str = "../backend_properties/content_index/content_index.json";
+
+// bigger files are not indexed
+const MAX_INDEXED_FILE_BYTES: u64 = 10 * 1024 * 1024;
+const DEFAULT_QUERY_LIMIT: usize = 100;
+
+// BM25 parameters
+const BM25_K1: f64 = 1.2;
+const BM25_B: f64 = 0.75;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct IndexedDoc {
+    path: String,
+    // unix timestamp (milliseconds)
+    modified: u64,
+    size: u64,
+    // number of tokens
+    length: u32,
+    terms: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct ContentIndex {
+    roots: Vec<String>,
+    docs: HashMap<u32, IndexedDoc>,
+    // term -> doc id -> token positions (ascending)
+    postings: HashMap<String, HashMap<u32, Vec<u32>>>,
+    total_length: u64,
+    next_id: u32,
+    // path -> doc id, rebuilt on load
+    #[serde(skip)]
+    doc_ids: HashMap<String, u32>,
+    #[serde(skip)]
+    loaded: bool,
+}
+
+static INDEX: Lazy<Mutex<ContentIndex>> = Lazy::new(|| Mutex::new(ContentIndex::default()));
+
+#[derive(Serialize, Debug, Default)]
+pub struct IndexUpdate {
+    added: usize,
+    updated: usize,
+    removed: usize,
+    unchanged: usize,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ContentIndexStatus {
+    roots: Vec<String>,
+    documents: usize,
+    terms: usize,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct IndexHit {
+    path: String,
+    score: f64,
+}
+
+fn is_hangul(c: char) -> bool {
+    matches!(c, '\u{AC00}'..='\u{D7A3}' | '\u{1100}'..='\u{11FF}' | '\u{3130}'..='\u{318F}')
+}
+
+fn flush_word(word: &mut String, tokens: &mut Vec<String>) {
+    if !word.is_empty() {
+        tokens.push(std::mem::take(word));
+    }
+}
+
+// Korean has no reliable word boundaries without a dictionary : index character bigrams
+fn flush_hangul(run: &mut Vec<char>, tokens: &mut Vec<String>) {
+    match run.len() {
+        0 => {}
+        1 => tokens.push(run[0].to_string()),
+        _ => tokens.extend(run.windows(2).map(|pair| pair.iter().collect::<String>())),
+    }
+    run.clear();
+}
+
+// lowercase words; Hangul runs become overlapping bigrams ("검색엔진" -> 검색, 색엔, 엔진)
+pub fn tokenize(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut word = String::new();
+    let mut hangul: Vec<char> = Vec::new();
+
+    for c in text.chars().flat_map(char::to_lowercase) {
+        if is_hangul(c) {
+            flush_word(&mut word, &mut tokens);
+            hangul.push(c);
+        } else if c.is_alphanumeric() {
+            flush_hangul(&mut hangul, &mut tokens);
+            word.push(c);
+        } else {
+            flush_word(&mut word, &mut tokens);
+            flush_hangul(&mut hangul, &mut tokens);
+        }
+    }
+    flush_word(&mut word, &mut tokens);
+    flush_hangul(&mut hangul, &mut tokens);
+    tokens
+}
+
+fn file_stamp(metadata: &fs::Metadata) -> (u64, u64) {
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0);
+    (modified, metadata.len())
+}
+
+fn is_indexable(path: &Path, metadata: &fs::Metadata) -> bool {
+    let is_text = mime_guess::from_path(path).first().map_or(false, |mime| mime.type_() == "text");
+    metadata.is_file() && is_text && metadata.len() <= MAX_INDEXED_FILE_BYTES
+}
+
+// text-like files under `dir` with their (mtime, size); symlinks are not followed
+fn list_indexable_files(dir: &Path, files: &mut Vec<(PathBuf, (u64, u64))>) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            println!("Skipping directory due to access error: {}", e);
+            return;
+        }
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let metadata = match fs::symlink_metadata(&path) {
+            Ok(meta) => meta,
+            Err(_) => continue,
+        };
+        if metadata.is_dir() {
+            list_indexable_files(&path, files);
+        } else if is_indexable(&path, &metadata) {
+            files.push((path, file_stamp(&metadata)));
+        }
+    }
+}
+
+impl ContentIndex {
+    fn ensure_loaded(&mut self) {
+        if self.loaded {
+            return;
+        }
+        if let Some(stored) = fs::read_to_string(CONTENT_INDEX_PATH)
+            .ok()
+            .and_then(|data| serde_json::from_str::<ContentIndex>(&data).ok())
+        {
+            *self = stored;
+        }
+        self.doc_ids = self.docs.iter().map(|(id, doc)| (doc.path.clone(), *id)).collect();
+        self.loaded = true;
+    }
+
+    fn save(&self) -> Result<(), String> {
+        if let Some(parent) = Path::new(CONTENT_INDEX_PATH).parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let data = serde_json::to_string(self).map_err(|e| e.to_string())?;
+        let temp_path = format!("{}.tmp", CONTENT_INDEX_PATH);
+        fs::write(&temp_path, data).map_err(|e| e.to_string())?;
+        fs::rename(&temp_path, CONTENT_INDEX_PATH).map_err(|e| e.to_string())
+    }
+
+    fn covers(&self, dir: &Path) -> bool {
+        self.roots.iter().any(|root| dir.starts_with(root))
+    }
+
+    fn remove_doc(&mut self, path: &str) -> bool {
+        let id = match self.doc_ids.remove(path) {
+            Some(id) => id,
+            None => return false,
+        };
+        let doc = match self.docs.remove(&id) {
+            Some(doc) => doc,
+            None => return false,
+        };
+        for term in &doc.terms {
+            if let Some(docs) = self.postings.get_mut(term) {
+                docs.remove(&id);
+                if docs.is_empty() {
+                    self.postings.remove(term);
+                }
+            }
+        }
+        self.total_length -= doc.length as u64;
+        true
+    }
+
+    fn add_doc(&mut self, path: String, (modified, size): (u64, u64), content: &str) {
+        self.remove_doc(&path);
+
+        let tokens = tokenize(content);
+        let mut positions: HashMap<&str, Vec<u32>> = HashMap::new();
+        for (position, token) in tokens.iter().enumerate() {
+            positions.entry(token.as_str()).or_default().push(position as u32);
+        }
+
+        let id = self.next_id;
+        self.next_id += 1;
+        let terms: Vec<String> = positions.keys().map(|term| term.to_string()).collect();
+        for (term, term_positions) in positions {
+            self.postings.entry(term.to_string()).or_default().insert(id, term_positions);
+        }
+
+        self.total_length += tokens.len() as u64;
+        self.doc_ids.insert(path.clone(), id);
+        self.docs.insert(id, IndexedDoc {
+            path,
+            modified,
+            size,
+            length: tokens.len() as u32,
+            terms,
+        });
+    }
+
+    // doc id -> number of times the tokens appear next to each other
+    fn phrase_matches(&self, tokens: &[String]) -> HashMap<u32, u32> {
+        let mut matches = HashMap::new();
+        let first = match tokens.first().and_then(|token| self.postings.get(token)) {
+            Some(first) => first,
+            None => return matches,
+        };
+
+        for (doc, starts) in first {
+            let count = starts
+                .iter()
+                .filter(|start| {
+                    tokens.iter().enumerate().skip(1).all(|(offset, token)| {
+                        self.postings
+                            .get(token)
+                            .and_then(|docs| docs.get(doc))
+                            .map_or(false, |positions| positions.binary_search(&(*start + offset as u32)).is_ok())
+                    })
+                })
+                .count() as u32;
+            if count > 0 {
+                matches.insert(*doc, count);
+            }
+        }
+        matches
+    }
+
+    // doc id -> occurrences of one Hangul syllable; runs of two syllables or more are indexed as bigrams only,
+    // so it is looked up in the bigrams starting with it (and ending with it, at the end of a run)
+    fn syllable_matches(&self, syllable: char) -> HashMap<u32, u32> {
+        let mut matches: HashMap<u32, u32> = HashMap::new();
+        // one-syllable runs
+        if let Some(docs) = self.postings.get(&syllable.to_string()) {
+            for (doc, positions) in docs {
+                *matches.entry(*doc).or_insert(0) += positions.len() as u32;
+            }
+        }
+
+        let mut starting: HashMap<u32, HashSet<u32>> = HashMap::new();
+        let mut ending: Vec<(u32, u32)> = Vec::new();
+        for (term, docs) in &self.postings {
+            let mut chars = term.chars();
+            let (first, second) = match (chars.next(), chars.next(), chars.next()) {
+                (Some(first), Some(second), None) if is_hangul(first) && is_hangul(second) => (first, second),
+                _ => continue,
+            };
+            for (doc, positions) in docs {
+                if first == syllable {
+                    starting.entry(*doc).or_default().extend(positions.iter().copied());
+                }
+                if second == syllable {
+                    ending.extend(positions.iter().map(|position| (*doc, *position)));
+                }
+            }
+        }
+
+        for (doc, positions) in &starting {
+            *matches.entry(*doc).or_insert(0) += positions.len() as u32;
+        }
+        // the syllable ends its run when no bigram starts with it right after
+        for (doc, position) in ending {
+            if !starting.get(&doc).map_or(false, |positions| positions.contains(&(position + 1))) {
+                *matches.entry(doc).or_insert(0) += 1;
+            }
+        }
+        matches
+    }
+
+    // doc id -> matches of one query term (its tokens in order)
+    fn term_matches(&self, tokens: &[String]) -> HashMap<u32, u32> {
+        if let [token] = tokens {
+            let mut chars = token.chars();
+            if let (Some(syllable), None) = (chars.next(), chars.next()) {
+                if is_hangul(syllable) {
+                    return self.syllable_matches(syllable);
+                }
+            }
+        }
+        self.phrase_matches(tokens)
+    }
+
+    fn bm25(&self, term_frequency: u32, document_frequency: usize, doc_length: u32) -> f64 {
+        let doc_count = self.docs.len() as f64;
+        let average_length = (self.total_length as f64 / doc_count).max(1.0);
+        let df = document_frequency as f64;
+        let idf = (1.0 + (doc_count - df + 0.5) / (df + 0.5)).ln();
+        let tf = term_frequency as f64;
+        idf * tf * (BM25_K1 + 1.0) / (tf + BM25_K1 * (1.0 - BM25_B + BM25_B * doc_length as f64 / average_length))
+    }
+
+    // every term (or "phrase") must appear, -term must not; best BM25 score first
+    fn query(&self, query: &str, dir: Option<&Path>, limit: usize) -> Vec<IndexHit> {
+        let terms = query_terms::parse_query(query);
+        let required: Vec<Vec<String>> = terms
+            .iter()
+            .filter(|term| !term.exclude)
+            .map(|term| tokenize(&term.text))
+            .filter(|tokens| !tokens.is_empty())
+            .collect();
+        if required.is_empty() {
+            return vec![];
+        }
+
+        // a term that tokenizes into several tokens (phrase, Korean word) must match them in order;
+        // a one-syllable Korean term matches inside longer words too
+        let required_matches: Vec<HashMap<u32, u32>> = required.iter().map(|tokens| self.term_matches(tokens)).collect();
+        let excluded: HashSet<u32> = terms
+            .iter()
+            .filter(|term| term.exclude)
+            .flat_map(|term| self.term_matches(&tokenize(&term.text)).into_keys())
+            .collect();
+
+        let mut hits: Vec<IndexHit> = required_matches[0]
+            .keys()
+            .filter(|doc| !excluded.contains(doc))
+            .filter(|doc| required_matches.iter().all(|matches| matches.contains_key(doc)))
+            .filter_map(|doc| self.docs.get(doc).map(|indexed| (doc, indexed)))
+            .filter(|(_, indexed)| dir.map_or(true, |dir| Path::new(&indexed.path).starts_with(dir)))
+            .map(|(doc, indexed)| {
+                let score = required_matches
+                    .iter()
+                    .map(|matches| self.bm25(matches[doc], matches.len(), indexed.length))
+                    .sum();
+                IndexHit { path: indexed.path.clone(), score }
+            })
+            .collect();
+
+        hits.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| a.path.cmp(&b.path))
+        });
+        hits.truncate(limit);
+        hits
+    }
+}
+
+// bring the index up to date under `dir` : only files whose mtime / size changed are read again
+async fn refresh(dir: &Path) -> Result<IndexUpdate, String> {
+    let walk_dir = dir.to_path_buf();
+    let files = tokio::task::spawn_blocking(move || {
+        let mut files = Vec::new();
+        list_indexable_files(&walk_dir, &mut files);
+        files
+    })
+    .await
+    .map_err(|e| format!("Task failed: {:?}", e))?;
+
+    let mut update = IndexUpdate::default();
+    let (changed, removed) = {
+        let mut index = INDEX.lock().map_err(|e| e.to_string())?;
+        index.ensure_loaded();
+
+        let mut seen = HashSet::new();
+        let mut changed = Vec::new();
+        for (path, stamp) in files {
+            let path_str = path.to_string_lossy().to_string();
+            let indexed = index.doc_ids.get(&path_str).and_then(|id| index.docs.get(id));
+            match indexed {
+                Some(doc) if (doc.modified, doc.size) == stamp => update.unchanged += 1,
+                Some(_) => {
+                    update.updated += 1;
+                    changed.push((path, stamp));
+                }
+                None => {
+                    update.added += 1;
+                    changed.push((path, stamp));
+                }
+            }
+            seen.insert(path_str);
+        }
+        let removed: Vec<String> = index
+            .doc_ids
+            .keys()
+            .filter(|path| Path::new(path).starts_with(dir) && !seen.contains(*path))
+            .cloned()
+            .collect();
+        (changed, removed)
+    };
+
+    // read outside the lock, apply in one go
+    let mut contents = Vec::with_capacity(changed.len());
+    for (path, stamp) in changed {
+        let content = read_text_content(&path).await.unwrap_or_default();
+        contents.push((path.to_string_lossy().to_string(), stamp, content));
+    }
+
+    let mut index = INDEX.lock().map_err(|e| e.to_string())?;
+    for path in removed {
+        if index.remove_doc(&path) {
+            update.removed += 1;
+        }
+    }
+    for (path, stamp, content) in contents {
+        index.add_doc(path, stamp, &content);
+    }
+    // most queries find nothing to refresh : no need to rewrite the whole index
+    if update.added + update.updated + update.removed > 0 {
+        index.save()?;
+    }
+
+    println!("Content index refreshed under {:?}: {:?}", dir, update);
+    Ok(update)
+}
+
+// custom_sch_method "5" : ranked index lookup instead of a walk (results are sent best first)
+pub async fn search_with_index(
+    dir: &Path,
+    keyword: &str,
+    options: &SearchOptions,
+    sink: &dyn ResultSink,
+) -> Result<(), String> {
+    {
+        let mut index = INDEX.lock().map_err(|e| e.to_string())?;
+        index.ensure_loaded();
+        if !index.covers(dir) {
+            return Err(format!("Folder is not in the content index: {:?}", dir));
+        }
+    }
+    refresh(dir).await?;
+
+    let hits = {
+        let index = INDEX.lock().map_err(|e| e.to_string())?;
+        index.query(keyword, Some(dir), usize::MAX)
+    };
+
+    // folders are never indexed : nothing to send for folder-only searches
+    if options.search_scope == "2" {
+        return Ok(());
+    }
+
+    for hit in hits {
+        let path = Path::new(&hit.path);
+        if options.custom_property_use && should_filter_file_by_metadata(path, options) {
+            continue;
+        }
+        let file_item = FileItem {
+            file_name: path.file_stem().and_then(|name| name.to_str()).unwrap_or_default().to_string(),
+            file_path: hit.path.clone(),
+            matched_ranges: vec![],
+            byte_offsets: vec![],
+            score: Some(hit.score),
+        };
+        sink.send(file_item).await?;
+    }
+    Ok(())
+}
+
+// true if `dir` is at or under one of the indexed roots
+pub fn covers_dir(dir: &Path) -> bool {
+    match INDEX.lock() {
+        Ok(mut index) => {
+            index.ensure_loaded();
+            index.covers(dir)
+        }
+        Err(_) => false,
+    }
+}
+
+// indexed file paths at or under `dir`
+pub fn indexed_paths_under(dir: &Path) -> Vec<String> {
+    let mut index = match INDEX.lock() {
+        Ok(index) => index,
+        Err(_) => return vec![],
+    };
+    index.ensure_loaded();
+    index
+        .doc_ids
+        .keys()
+        .filter(|path| Path::new(path).starts_with(dir))
+        .cloned()
+        .collect()
+}
+
+/// 내용 색인 생성 / 갱신
+///
+/// 폴더를 색인 대상에 추가하고, 수정 시각이나 크기가 바뀐 텍스트 파일만 다시 읽어 색인
+///
+/// # Arguments
+///
+/// * `roots` - 색인할 폴더 목록 (비어 있으면 기존 폴더 전체를 갱신)
+///
+/// # Returns
+///
+/// * `Result<IndexUpdate, String>` - 추가 / 갱신 / 삭제 / 변경 없음 파일 수
+#[tauri::command]
+pub async fn build_content_index(roots: Vec<String>) -> Result<IndexUpdate, String> {
+    if let Some(missing) = roots.iter().find(|root| !Path::new(root).is_dir()) {
+        return Err(format!("Directory does not exist: {}", missing));
+    }
+
+    let roots = {
+        let mut index = INDEX.lock().map_err(|e| e.to_string())?;
+        index.ensure_loaded();
+        for root in roots.iter() {
+            if !index.roots.contains(root) {
+                index.roots.push(root.clone());
+            }
+        }
+        if roots.is_empty() { index.roots.clone() } else { roots }
+    };
+
+    let mut total = IndexUpdate::default();
+    for root in roots {
+        let update = refresh(Path::new(&root)).await?;
+        total.added += update.added;
+        total.updated += update.updated;
+        total.removed += update.removed;
+        total.unchanged += update.unchanged;
+    }
+    Ok(total)
+}
+
+/// 색인 대상 폴더 제거 (해당 폴더의 색인도 삭제)
+#[tauri::command]
+pub fn remove_content_index_root(root: String) -> Result<(), String> {
+    let mut index = INDEX.lock().map_err(|e| e.to_string())?;
+    index.ensure_loaded();
+    index.roots.retain(|other| other != &root);
+
+    let still_covered: Vec<String> = index.roots.clone();
+    let orphaned: Vec<String> = index
+        .doc_ids
+        .keys()
+        .filter(|path| Path::new(path).starts_with(&root))
+        .filter(|path| !still_covered.iter().any(|other| Path::new(path).starts_with(other)))
+        .cloned()
+        .collect();
+    for path in orphaned {
+        index.remove_doc(&path);
+    }
+    index.save()
+}
+
+#[tauri::command]
+pub fn get_content_index_status() -> Result<ContentIndexStatus, String> {
+    let mut index = INDEX.lock().map_err(|e| e.to_string())?;
+    index.ensure_loaded();
+    Ok(ContentIndexStatus {
+        roots: index.roots.clone(),
+        documents: index.docs.len(),
+        terms: index.postings.len(),
+    })
+}
+
+/// 내용 색인 검색
+///
+/// # Arguments
+///
+/// * `query` - 검색어 (모든 단어 포함, "구문"은 순서대로, -단어는 제외)
+/// * `directory` - 이 폴더 아래 파일만 (없으면 전체)
+/// * `limit` - 최대 개수 (기본 100)
+///
+/// # Returns
+///
+/// * `Result<Vec<IndexHit>, String>` - BM25 점수 내림차순
+#[tauri::command]
+pub fn query_content_index(query: String, directory: Option<String>, limit: Option<usize>) -> Result<Vec<IndexHit>, String> {
+    let mut index = INDEX.lock().map_err(|e| e.to_string())?;
+    index.ensure_loaded();
+    Ok(index.query(&query, directory.as_deref().map(Path::new), limit.unwrap_or(DEFAULT_QUERY_LIMIT)))
+}
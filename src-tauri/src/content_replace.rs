@@ -0,0 +1,719 @@
+// Regex search-and-replace over file contents : preview -> apply -> (rollback)
+//
+// The preview finds the files with the regex matcher of the search engine and keeps a plan in memory.
+// Apply re-reads every planned file, skips the ones that changed since the preview, backs the
+// original up and writes the new content atomically in the file's own encoding and line endings.
+// Rollback puts the backups back.
+
+use chrono::Local;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, UNIX_EPOCH};
+
+use crate::background_io;
+use crate::hard_links;
+use crate::result_sink::CollectorSink;
+use crate::search::SearchOptions;
+use crate::search_engine::{compile_regex, search_into};
+use crate::text_encoding::{self, LineEnding, TextEncoding};
+
+const REPLACE_BACKUP_DIR: &str = "../backend_properties/replace_backups";
+const OPERATION_FILE_NAME: &str = "operation.json";
+
+// bigger files are skipped
+const MAX_REPLACE_FILE_BYTES: u64 = 20 * 1024 * 1024;
+// hunks returned per file by the preview; the counts cover every replacement
+const PREVIEW_HUNK_LIMIT: usize = 100;
+
+// previews older than this (or beyond the newest PREVIEW_LIMIT) can no longer be applied
+const PREVIEW_TTL: Duration = Duration::from_secs(30 * 60);
+const PREVIEW_LIMIT: usize = 10;
+
+// previews waiting to be applied, by preview id
+static PREVIEWS: Lazy<Mutex<HashMap<String, ReplacePlan>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+// size + mtime : a file that still has them is assumed unchanged
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+struct Fingerprint {
+    size: u64,
+    // unix timestamp (milliseconds)
+    modified: u64,
+}
+
+fn fingerprint(path: &Path) -> Option<Fingerprint> {
+    let metadata = fs::metadata(path).ok()?;
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0);
+    Some(Fingerprint { size: metadata.len(), modified })
+}
+
+struct PlannedFile {
+    path: PathBuf,
+    fingerprint: Fingerprint,
+}
+
+struct ReplacePlan {
+    pattern: String,
+    replacement: String,
+    files: Vec<PlannedFile>,
+    created_at: Instant,
+}
+
+fn store_preview(id: String, plan: ReplacePlan) -> Result<(), String> {
+    let mut previews = PREVIEWS.lock().map_err(|e| e.to_string())?;
+    previews.insert(id, plan);
+    previews.retain(|_, plan| plan.created_at.elapsed() < PREVIEW_TTL);
+
+    // drop the oldest ones
+    while previews.len() > PREVIEW_LIMIT {
+        let oldest = previews.iter().min_by_key(|(_, plan)| plan.created_at).map(|(id, _)| id.clone());
+        match oldest {
+            Some(id) => {
+                previews.remove(&id);
+            }
+            None => break,
+        }
+    }
+    Ok(())
+}
+
+// the preview leaves the map : a preview is applied at most once
+fn take_preview(id: &str) -> Result<ReplacePlan, String> {
+    let plan = PREVIEWS
+        .lock()
+        .map_err(|e| e.to_string())?
+        .remove(id)
+        .ok_or_else(|| format!("Replace preview not found or already applied: {}", id))?;
+    if plan.created_at.elapsed() >= PREVIEW_TTL {
+        return Err(format!("Replace preview expired: {}", id));
+    }
+    Ok(plan)
+}
+
+// changed lines around one or more neighbouring replacements (line endings not included)
+#[derive(Serialize, Debug, Clone)]
+pub struct DiffHunk {
+    // first line, 1-based
+    line: usize,
+    before: String,
+    after: String,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct FilePreview {
+    path: String,
+    encoding: TextEncoding,
+    line_ending: LineEnding,
+    replacements: usize,
+    hunks: Vec<DiffHunk>,
+    // more than PREVIEW_HUNK_LIMIT hunks
+    hunks_truncated: bool,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct SkippedFile {
+    path: String,
+    reason: String,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct ReplacePreview {
+    id: String,
+    pattern: String,
+    replacement: String,
+    files: Vec<FilePreview>,
+    skipped: Vec<SkippedFile>,
+    replacements: usize,
+}
+
+// one replaced file of an operation : where its original is kept
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct BackupEntry {
+    path: String,
+    backup: String,
+    // None until the new content is written
+    written: Option<Fingerprint>,
+}
+
+// stored next to the backups as operation.json
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct ReplaceOperation {
+    id: String,
+    pattern: String,
+    replacement: String,
+    // unix timestamp (seconds)
+    applied_at: i64,
+    files: Vec<BackupEntry>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct ReplaceOperationSummary {
+    id: String,
+    pattern: String,
+    replacement: String,
+    applied_at: i64,
+    // files not rolled back yet
+    files: usize,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct ReplaceResult {
+    operation_id: String,
+    replaced: Vec<String>,
+    skipped: Vec<SkippedFile>,
+    replacements: usize,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct RollbackResult {
+    restored: Vec<String>,
+    skipped: Vec<SkippedFile>,
+}
+
+struct Replaced {
+    text: String,
+    count: usize,
+    hunks: Vec<DiffHunk>,
+    hunks_truncated: bool,
+}
+
+// start of the line holding `pos`
+fn line_start(text: &str, pos: usize) -> usize {
+    text[..pos].rfind('\n').map_or(0, |found| found + 1)
+}
+
+// just past the line break ending the line holding `pos` (or the end of the text)
+fn line_end(text: &str, pos: usize) -> usize {
+    text[pos..].find('\n').map_or(text.len(), |found| pos + found + 1)
+}
+
+fn hunk_text(text: &str) -> String {
+    text.trim_end_matches(['\r', '\n']).to_string()
+}
+
+// every match of `regex` replaced ($1 / ${name} expanded); matches on the same lines share a hunk
+fn replace_text(regex: &Regex, replacement: &str, text: &str, ending: LineEnding) -> Replaced {
+    let mut replaced = Replaced {
+        text: String::with_capacity(text.len()),
+        count: 0,
+        hunks: vec![],
+        hunks_truncated: false,
+    };
+    // copied from text up to here
+    let mut last = 0;
+    // open hunk : (line start in text, start in the new text, end of its last line in text)
+    let mut hunk: Option<(usize, usize, usize)> = None;
+    // line number of `counted_to`
+    let mut line = 1;
+    let mut counted_to = 0;
+
+    let mut close_hunk = |replaced: &mut Replaced, last: &mut usize, (start, new_start, end): (usize, usize, usize)| {
+        replaced.text.push_str(&text[*last..end]);
+        *last = end;
+        line += text[counted_to..start].matches('\n').count();
+        counted_to = start;
+        if replaced.hunks.len() < PREVIEW_HUNK_LIMIT {
+            replaced.hunks.push(DiffHunk {
+                line,
+                before: hunk_text(&text[start..end]),
+                after: hunk_text(&replaced.text[new_start..]),
+            });
+        } else {
+            replaced.hunks_truncated = true;
+        }
+    };
+
+    for caps in regex.captures_iter(text) {
+        let found = match caps.get(0) {
+            Some(found) => found,
+            None => continue,
+        };
+        let mut expanded = String::new();
+        caps.expand(replacement, &mut expanded);
+        // line breaks typed in the replacement follow the file
+        let expanded = text_encoding::with_line_ending(&expanded, ending);
+        if expanded == found.as_str() {
+            continue;
+        }
+
+        let start = line_start(text, found.start());
+        // a match ending with a line break ends its hunk on that line
+        let end = if found.as_str().ends_with('\n') { found.end() } else { line_end(text, found.end()) };
+        match hunk {
+            Some((hunk_start, new_start, hunk_end)) if start < hunk_end => {
+                hunk = Some((hunk_start, new_start, hunk_end.max(end)));
+            }
+            open => {
+                if let Some(open) = open {
+                    close_hunk(&mut replaced, &mut last, open);
+                }
+                replaced.text.push_str(&text[last..start]);
+                last = start;
+                hunk = Some((start, replaced.text.len(), end));
+            }
+        }
+
+        replaced.text.push_str(&text[last..found.start()]);
+        replaced.text.push_str(&expanded);
+        last = found.end();
+        replaced.count += 1;
+    }
+
+    if let Some(open) = hunk {
+        close_hunk(&mut replaced, &mut last, open);
+    }
+    replaced.text.push_str(&text[last..]);
+    replaced
+}
+
+// decoded content of a file that can be rewritten, or why it cannot
+fn read_replaceable(path: &Path) -> Result<(Vec<u8>, String, TextEncoding), String> {
+    let metadata = fs::metadata(path).map_err(|e| e.to_string())?;
+    if metadata.len() > MAX_REPLACE_FILE_BYTES {
+        return Err(format!("File is larger than {} bytes", MAX_REPLACE_FILE_BYTES));
+    }
+    // the rename below would give this name its own copy and leave the other links unchanged
+    if hard_links::is_hard_linked(&metadata) {
+        return Err("File has other hard links".to_string());
+    }
+    let bytes = fs::read(path).map_err(|e| e.to_string())?;
+    let (text, encoding) = text_encoding::decode(&bytes).ok_or("Not UTF-8 or UTF-16 text")?;
+    Ok((bytes, text, encoding))
+}
+
+// owner of the replaced file : a file of another user can only be handed back by root, so the
+// write fails rather than take the file over
+#[cfg(unix)]
+fn copy_owner(file: &File, metadata: &fs::Metadata) -> std::io::Result<()> {
+    use std::os::unix::fs::MetadataExt;
+    use std::os::unix::io::AsRawFd;
+
+    let created = file.metadata()?;
+    if created.uid() == metadata.uid() && created.gid() == metadata.gid() {
+        return Ok(());
+    }
+    if unsafe { libc::fchown(file.as_raw_fd(), metadata.uid(), metadata.gid()) } != 0 {
+        let e = std::io::Error::last_os_error();
+        return Err(std::io::Error::new(e.kind(), format!("Cannot keep the file owner: {}", e)));
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn copy_owner(_file: &File, _metadata: &fs::Metadata) -> std::io::Result<()> {
+    // the new file is created in the same folder and inherits its ACLs
+    Ok(())
+}
+
+// extended attributes of the replaced file (user tags, ACLs, ...)
+#[cfg(unix)]
+fn copy_xattrs(from: &Path, to: &Path) -> std::io::Result<()> {
+    let names = match xattr::list(from) {
+        Ok(names) => names,
+        // nothing to keep on file systems without them
+        Err(e) if e.raw_os_error() == Some(libc::ENOTSUP) => return Ok(()),
+        Err(e) => return Err(e),
+    };
+    for name in names {
+        let value = match xattr::get(from, &name)? {
+            Some(value) => value,
+            None => continue,
+        };
+        // e.g. a security label the new file already got
+        if xattr::get(to, &name).ok().flatten().as_deref() == Some(value.as_slice()) {
+            continue;
+        }
+        xattr::set(to, &name, &value).map_err(|e| {
+            std::io::Error::new(e.kind(), format!("Cannot keep the attribute {}: {}", name.to_string_lossy(), e))
+        })?;
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn copy_xattrs(_from: &Path, _to: &Path) -> std::io::Result<()> {
+    Ok(())
+}
+
+// write a temporary file next to `path` and rename it over `path`
+// the temporary file takes the owner, extended attributes and permissions of `path` first
+fn write_atomically(path: &Path, bytes: &[u8]) -> Result<(), String> {
+    let file_name = path.file_name().ok_or_else(|| format!("Invalid file path: {:?}", path))?;
+    let temp_path = path.with_file_name(format!(".{}.replace-tmp", file_name.to_string_lossy()));
+    let metadata = fs::metadata(path).map_err(|e| e.to_string())?;
+
+    let result = (|| {
+        let mut file = File::create(&temp_path)?;
+        file.write_all(bytes)?;
+        copy_owner(&file, &metadata)?;
+        copy_xattrs(path, &temp_path)?;
+        // after the owner : chown clears the set-user-id bits
+        fs::set_permissions(&temp_path, metadata.permissions())?;
+        file.sync_all()?;
+        fs::rename(&temp_path, path)
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result.map_err(|e| e.to_string())
+}
+
+fn operation_dir(backup_dir: &Path, id: &str) -> PathBuf {
+    backup_dir.join(id)
+}
+
+// operation ids are uuids; anything else (e.g. "..") never names an operation folder
+fn is_operation_id(id: &str) -> bool {
+    uuid::Uuid::parse_str(id).is_ok()
+}
+
+fn read_operation(backup_dir: &Path, id: &str) -> Result<ReplaceOperation, String> {
+    if !is_operation_id(id) {
+        return Err(format!("Invalid replace operation id: {}", id));
+    }
+    let data = fs::read_to_string(operation_dir(backup_dir, id).join(OPERATION_FILE_NAME))
+        .map_err(|e| format!("Replace operation not found: {} ({})", id, e))?;
+    let operation: ReplaceOperation = serde_json::from_str(&data).map_err(|e| e.to_string())?;
+    // write_operation goes by the stored id
+    if operation.id != id {
+        return Err(format!("Replace operation does not match its folder: {}", id));
+    }
+    Ok(operation)
+}
+
+fn write_operation(backup_dir: &Path, operation: &ReplaceOperation) -> Result<(), String> {
+    let data = serde_json::to_string_pretty(operation).map_err(|e| e.to_string())?;
+    fs::write(operation_dir(backup_dir, &operation.id).join(OPERATION_FILE_NAME), data).map_err(|e| e.to_string())
+}
+
+// candidate files : the regex content search of the engine, files only, every path once
+// every file that decodes as text is searched (.json, .csv, ... are not text/* for the MIME check)
+async fn find_candidates(directory: &Path, pattern: &str, options: &SearchOptions) -> Result<Vec<PathBuf>, String> {
+    let mut options = options.clone();
+    options.custom_sch_method = "1".to_string();
+    options.custom_file_cont_use = true;
+    options.content_any_text = true;
+    options.search_scope = "1".to_string();
+    options.multi_term_use = false;
+
+    let sink = Arc::new(CollectorSink::new());
+    search_into(directory, pattern, &options, sink.clone()).await?;
+
+    let mut seen = HashSet::new();
+    let mut candidates: Vec<PathBuf> = sink
+        .take()
+        .into_iter()
+        // symlinked names are replaced through their target
+        .filter_map(|file_item| fs::canonicalize(&file_item.file_path).ok())
+        .filter(|path| seen.insert(path.clone()))
+        .collect();
+    candidates.sort();
+    Ok(candidates)
+}
+
+/// 내용 바꾸기 미리보기
+///
+/// 정규식 검색(검색 방법 "1")으로 내용이 일치하는 파일을 찾고, 파일별로 바뀔 줄을 보여줌.
+/// 파일은 아직 바뀌지 않으며, 반환된 id로 apply_replace를 호출해야 적용됨 (최근 미리보기 10개를 30분 동안 유지)
+///
+/// # Arguments
+///
+/// * `directory` - 검색할 폴더
+/// * `pattern` - 정규식 (Rust regex 문법)
+/// * `replacement` - 바꿀 내용 ($1, ${name} 으로 캡처 그룹 사용, $$ 는 $)
+/// * `options` - 검색 옵션 (검색 방법 / 범위 / 내용 검색 여부는 무시됨)
+///
+/// # Returns
+///
+/// * `Result<ReplacePreview, String>` - 미리보기 id, 파일별 변경 내용, 건너뛴 파일
+#[tauri::command]
+pub async fn preview_replace(
+    directory: String,
+    pattern: String,
+    replacement: String,
+    options: SearchOptions,
+) -> Result<ReplacePreview, String> {
+    let regex = compile_regex(&pattern)?;
+    let candidates = find_candidates(Path::new(&directory), &pattern, &options).await?;
+
+    let replacement_for_job = replacement.clone();
+    let (planned, files, skipped) = background_io::spawn_blocking(options.is_background(), move || {
+        let mut planned = Vec::new();
+        let mut files = Vec::new();
+        let mut skipped = Vec::new();
+        for path in candidates {
+            let path_text = path.to_string_lossy().to_string();
+            let file_fingerprint = match fingerprint(&path) {
+                Some(file_fingerprint) => file_fingerprint,
+                None => continue,
+            };
+            let (_, text, encoding) = match read_replaceable(&path) {
+                Ok(content) => content,
+                Err(reason) => {
+                    skipped.push(SkippedFile { path: path_text, reason });
+                    continue;
+                }
+            };
+
+            let line_ending = text_encoding::line_ending(&text);
+            let replaced = replace_text(&regex, &replacement_for_job, &text, line_ending);
+            // a file name match, or every match already reads like its replacement
+            if replaced.count == 0 {
+                continue;
+            }
+
+            files.push(FilePreview {
+                path: path_text,
+                encoding,
+                line_ending,
+                replacements: replaced.count,
+                hunks: replaced.hunks,
+                hunks_truncated: replaced.hunks_truncated,
+            });
+            planned.push(PlannedFile { path, fingerprint: file_fingerprint });
+        }
+        (planned, files, skipped)
+    })
+    .await
+    .map_err(|e| format!("Task failed: {:?}", e))?;
+
+    let preview = ReplacePreview {
+        id: uuid::Uuid::new_v4().to_string(),
+        replacements: files.iter().map(|file| file.replacements).sum(),
+        pattern: pattern.clone(),
+        replacement: replacement.clone(),
+        files,
+        skipped,
+    };
+    println!(
+        "Replace preview {}: {} replacements in {} files",
+        preview.id,
+        preview.replacements,
+        preview.files.len()
+    );
+
+    store_preview(
+        preview.id.clone(),
+        ReplacePlan { pattern, replacement, files: planned, created_at: Instant::now() },
+    )?;
+    Ok(preview)
+}
+
+/// 내용 바꾸기 적용
+///
+/// 미리보기 이후 바뀐 파일은 건너뜀. 원본은 백업해 두고, 새 내용은 원래 인코딩(BOM 포함)과 줄바꿈으로
+/// 임시 파일에 쓴 뒤 (소유자, 확장 속성, 권한은 원본과 같게) 이름을 바꿔 원자적으로 교체.
+/// 소유자나 확장 속성을 옮길 수 없는 파일은 건너뜀
+///
+/// # Arguments
+///
+/// * `preview_id` - preview_replace가 반환한 id (한 번만, 30분 안에 적용 가능)
+/// * `files` - 바꿀 파일 경로 (없으면 미리보기의 모든 파일)
+///
+/// # Returns
+///
+/// * `Result<ReplaceResult, String>` - 되돌리기에 쓸 작업 id, 바뀐 파일, 건너뛴 파일
+#[tauri::command]
+pub async fn apply_replace(preview_id: String, files: Option<Vec<String>>) -> Result<ReplaceResult, String> {
+    apply_replace_in(PathBuf::from(REPLACE_BACKUP_DIR), preview_id, files).await
+}
+
+// apply_replace with the backups under `backup_dir`
+pub async fn apply_replace_in(
+    backup_dir: PathBuf,
+    preview_id: String,
+    files: Option<Vec<String>>,
+) -> Result<ReplaceResult, String> {
+    let plan = take_preview(&preview_id)?;
+    let regex = compile_regex(&plan.pattern)?;
+    let selected: Option<HashSet<String>> = files.map(|files| files.into_iter().collect());
+
+    tokio::task::spawn_blocking(move || {
+        let mut operation = ReplaceOperation {
+            id: uuid::Uuid::new_v4().to_string(),
+            pattern: plan.pattern.clone(),
+            replacement: plan.replacement.clone(),
+            applied_at: Local::now().timestamp(),
+            files: vec![],
+        };
+        fs::create_dir_all(operation_dir(&backup_dir, &operation.id)).map_err(|e| e.to_string())?;
+        write_operation(&backup_dir, &operation)?;
+
+        let mut result = ReplaceResult {
+            operation_id: operation.id.clone(),
+            replaced: vec![],
+            skipped: vec![],
+            replacements: 0,
+        };
+
+        for (index, planned) in plan.files.iter().enumerate() {
+            let path_text = planned.path.to_string_lossy().to_string();
+            if selected.as_ref().map_or(false, |selected| !selected.contains(&path_text)) {
+                continue;
+            }
+            let mut skip = |reason: String| result.skipped.push(SkippedFile { path: path_text.clone(), reason });
+
+            if fingerprint(&planned.path) != Some(planned.fingerprint) {
+                skip("File changed since the preview".to_string());
+                continue;
+            }
+            let (original, text, encoding) = match read_replaceable(&planned.path) {
+                Ok(content) => content,
+                Err(reason) => {
+                    skip(reason);
+                    continue;
+                }
+            };
+            let replaced = replace_text(&regex, &plan.replacement, &text, text_encoding::line_ending(&text));
+            if replaced.count == 0 {
+                skip("Nothing to replace".to_string());
+                continue;
+            }
+
+            // backup first, and listed before the file is touched
+            let backup = format!("{}.bak", index);
+            if let Err(e) = fs::write(operation_dir(&backup_dir, &operation.id).join(&backup), &original) {
+                skip(format!("Backup failed: {}", e));
+                continue;
+            }
+            operation.files.push(BackupEntry { path: path_text.clone(), backup, written: None });
+            write_operation(&backup_dir, &operation)?;
+
+            match write_atomically(&planned.path, &text_encoding::encode(&replaced.text, encoding)) {
+                Ok(()) => {
+                    if let Some(entry) = operation.files.last_mut() {
+                        entry.written = fingerprint(&planned.path);
+                    }
+                    result.replacements += replaced.count;
+                    result.replaced.push(path_text.clone());
+                }
+                Err(e) => {
+                    if let Some(entry) = operation.files.pop() {
+                        let _ = fs::remove_file(operation_dir(&backup_dir, &operation.id).join(entry.backup));
+                    }
+                    skip(format!("Write failed: {}", e));
+                }
+            }
+            write_operation(&backup_dir, &operation)?;
+        }
+
+        println!(
+            "Replace operation {}: {} replacements in {} files, {} skipped",
+            operation.id,
+            result.replacements,
+            result.replaced.len(),
+            result.skipped.len()
+        );
+        Ok(result)
+    })
+    .await
+    .map_err(|e| format!("Task failed: {:?}", e))?
+}
+
+/// 내용 바꾸기 되돌리기
+///
+/// 백업해 둔 원본으로 복원. 바꾼 뒤에 다시 수정된 파일은 건너뛰고 남겨둠 (나중에 다시 되돌리기 가능)
+///
+/// # Arguments
+///
+/// * `operation_id` - apply_replace가 반환한 작업 id
+///
+/// # Returns
+///
+/// * `Result<RollbackResult, String>` - 복원된 파일, 건너뛴 파일
+#[tauri::command]
+pub async fn rollback_replace(operation_id: String) -> Result<RollbackResult, String> {
+    rollback_replace_in(PathBuf::from(REPLACE_BACKUP_DIR), operation_id).await
+}
+
+// rollback_replace with the backups under `backup_dir`
+pub async fn rollback_replace_in(backup_dir: PathBuf, operation_id: String) -> Result<RollbackResult, String> {
+    // the folder of this id is removed below
+    if !is_operation_id(&operation_id) {
+        return Err(format!("Invalid replace operation id: {}", operation_id));
+    }
+    tokio::task::spawn_blocking(move || {
+        let mut operation = read_operation(&backup_dir, &operation_id)?;
+        let mut result = RollbackResult { restored: vec![], skipped: vec![] };
+        let mut remaining = Vec::new();
+
+        for entry in operation.files.drain(..) {
+            let path = Path::new(&entry.path);
+            // written : None -> the write may not have happened; the backup is the original either way
+            if entry.written.is_some() && fingerprint(path) != entry.written {
+                result.skipped.push(SkippedFile { path: entry.path.clone(), reason: "File changed after the replace".to_string() });
+                remaining.push(entry);
+                continue;
+            }
+            let restored = fs::read(operation_dir(&backup_dir, &operation_id).join(&entry.backup))
+                .map_err(|e| e.to_string())
+                .and_then(|original| write_atomically(path, &original));
+            match restored {
+                Ok(()) => result.restored.push(entry.path.clone()),
+                Err(e) => {
+                    result.skipped.push(SkippedFile { path: entry.path.clone(), reason: format!("Restore failed: {}", e) });
+                    remaining.push(entry);
+                }
+            }
+        }
+
+        if remaining.is_empty() {
+            fs::remove_dir_all(operation_dir(&backup_dir, &operation_id)).map_err(|e| e.to_string())?;
+        } else {
+            operation.files = remaining;
+            write_operation(&backup_dir, &operation)?;
+        }
+
+        println!(
+            "Replace operation {} rolled back: {} restored, {} skipped",
+            operation_id,
+            result.restored.len(),
+            result.skipped.len()
+        );
+        Ok(result)
+    })
+    .await
+    .map_err(|e| format!("Task failed: {:?}", e))?
+}
+
+/// 되돌릴 수 있는 내용 바꾸기 작업 목록
+///
+/// # Returns
+///
+/// * `Vec<ReplaceOperationSummary>` - 작업 목록 (최근 순)
+#[tauri::command]
+pub fn list_replace_operations() -> Vec<ReplaceOperationSummary> {
+    list_replace_operations_in(Path::new(REPLACE_BACKUP_DIR))
+}
+
+// list_replace_operations with the backups under `backup_dir`
+pub fn list_replace_operations_in(backup_dir: &Path) -> Vec<ReplaceOperationSummary> {
+    let entries = match fs::read_dir(backup_dir) {
+        Ok(entries) => entries,
+        Err(_) => return vec![],
+    };
+    let mut operations: Vec<ReplaceOperationSummary> = entries
+        .flatten()
+        .filter_map(|entry| read_operation(backup_dir, &entry.file_name().to_string_lossy()).ok())
+        .filter(|operation| !operation.files.is_empty())
+        .map(|operation| ReplaceOperationSummary {
+            files: operation.files.len(),
+            id: operation.id,
+            pattern: operation.pattern,
+            replacement: operation.replacement,
+            applied_at: operation.applied_at,
+        })
+        .collect();
+    operations.sort_by_key(|operation| std::cmp::Reverse(operation.applied_at));
+    operations
+}
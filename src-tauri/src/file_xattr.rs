@@ -0,0 +1,70 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::search::SearchOptions;
+
+// UTF-8 values as they are, anything else as hex ("0x0a1b...")
+fn value_text(value: &[u8]) -> String {
+    match std::str::from_utf8(value) {
+        Ok(text) => text.trim_end_matches('\0').to_string(),
+        Err(_) => format!("0x{}", value.iter().map(|byte| format!("{:02x}", byte)).collect::<String>()),
+    }
+}
+
+// every extended attribute of the file (symlinks are followed), name -> value
+#[cfg(unix)]
+pub fn read_xattrs(path: &Path) -> BTreeMap<String, String> {
+    let names = match xattr::list_deref(path) {
+        Ok(names) => names,
+        Err(e) => {
+            println!("Failed to list extended attributes of {:?}: {}", path, e);
+            return BTreeMap::new();
+        }
+    };
+    names
+        .filter_map(|name| {
+            let value = xattr::get_deref(path, &name).ok().flatten()?;
+            Some((name.to_string_lossy().to_string(), value_text(&value)))
+        })
+        .collect()
+}
+
+#[cfg(not(unix))]
+pub fn read_xattrs(_path: &Path) -> BTreeMap<String, String> {
+    // no user xattrs here (NTFS alternate streams are a different thing)
+    BTreeMap::new()
+}
+
+#[cfg(unix)]
+fn read_xattr(path: &Path, name: &str) -> Option<String> {
+    xattr::get_deref(path, name).ok().flatten().map(|value| value_text(&value))
+}
+
+#[cfg(not(unix))]
+fn read_xattr(_path: &Path, _name: &str) -> Option<String> {
+    None
+}
+
+// xattrMatch "0" : the attribute exists, "1" : its value equals xattrValue, "2" : its value contains xattrValue
+pub fn is_filtered_by_xattr(path: &Path, options: &SearchOptions) -> bool {
+    if options.xattr_name.is_empty() {
+        return false;
+    }
+    let value = match read_xattr(path, &options.xattr_name) {
+        Some(value) => value,
+        None => {
+            println!("File or folder filtered by missing xattr {}: {:?}", options.xattr_name, path);
+            return true;
+        }
+    };
+
+    let matched = match options.xattr_match.as_str() {
+        "1" => value == options.xattr_value,
+        "2" => value.to_lowercase().contains(&options.xattr_value.to_lowercase()),
+        _ => true,
+    };
+    if !matched {
+        println!("File or folder filtered by xattr {}: {}", options.xattr_name, value);
+    }
+    !matched
+}
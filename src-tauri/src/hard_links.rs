@@ -0,0 +1,139 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// where a file's data lives : every hard link of it has the same (device, inode)
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+pub struct LinkInfo {
+    pub inode: u64,
+    pub device: u64,
+    pub link_count: u64,
+}
+
+#[cfg(unix)]
+pub fn link_info(metadata: &fs::Metadata) -> Option<LinkInfo> {
+    use std::os::unix::fs::MetadataExt;
+    Some(LinkInfo {
+        inode: metadata.ino(),
+        device: metadata.dev(),
+        link_count: metadata.nlink(),
+    })
+}
+
+#[cfg(not(unix))]
+pub fn link_info(_metadata: &fs::Metadata) -> Option<LinkInfo> {
+    // the file index is not exposed by std here
+    None
+}
+
+// a file with more than one name
+pub fn is_hard_linked(metadata: &fs::Metadata) -> bool {
+    metadata.is_file() && link_info(metadata).map_or(false, |info| info.link_count > 1)
+}
+
+#[derive(Serialize, Debug)]
+pub struct HardLinkGroup {
+    #[serde(flatten)]
+    info: LinkInfo,
+    // names found under the root; fewer than link_count if some live elsewhere
+    paths: Vec<String>,
+}
+
+// hard-linked files under `dir` (symlinks are not followed, other devices are skipped)
+fn collect_linked_files(dir: &Path, device: Option<u64>, found: &mut Vec<(PathBuf, LinkInfo)>) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            println!("Skipping directory due to access error: {}", e);
+            return;
+        }
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let metadata = match fs::symlink_metadata(&path) {
+            Ok(meta) => meta,
+            Err(_) => continue,
+        };
+        let info = match link_info(&metadata) {
+            Some(info) => info,
+            None => continue,
+        };
+        if device.map_or(false, |device| device != info.device) {
+            continue;
+        }
+
+        if metadata.is_dir() {
+            collect_linked_files(&path, device, found);
+        } else if is_hard_linked(&metadata) {
+            found.push((path, info));
+        }
+    }
+}
+
+/// 하드 링크 찾기
+///
+/// 폴더 안에서 같은 inode를 가리키는 경로들을 찾음
+///
+/// # Arguments
+///
+/// * `root` - 찾을 폴더
+/// * `file_path` - 이 파일의 하드 링크만 찾음 (없으면 폴더 안의 모든 하드 링크 묶음)
+///
+/// # Returns
+///
+/// * `Result<Vec<HardLinkGroup>, String>` - inode별 경로 묶음 (경로가 많은 순)
+#[tauri::command]
+pub async fn find_hard_links(root: String, file_path: Option<String>) -> Result<Vec<HardLinkGroup>, String> {
+    let root = PathBuf::from(root);
+    if !root.is_dir() {
+        return Err(format!("Directory does not exist: {:?}", root));
+    }
+
+    let target = match file_path {
+        Some(file_path) => {
+            let metadata = fs::metadata(&file_path).map_err(|e| e.to_string())?;
+            let info = link_info(&metadata).ok_or("Hard link information is not available on this platform")?;
+            Some((file_path, info))
+        }
+        None => None,
+    };
+
+    // one device only when looking for a single file : links never cross file systems
+    let device = target.as_ref().map(|(_, info)| info.device);
+    let walk_root = root.clone();
+    let found = tokio::task::spawn_blocking(move || {
+        let mut found = Vec::new();
+        collect_linked_files(&walk_root, device, &mut found);
+        found
+    })
+    .await
+    .map_err(|e| format!("Task failed: {:?}", e))?;
+
+    let mut groups: HashMap<(u64, u64), HardLinkGroup> = HashMap::new();
+    for (path, info) in found {
+        groups
+            .entry((info.device, info.inode))
+            .or_insert_with(|| HardLinkGroup { info, paths: Vec::new() })
+            .paths
+            .push(path.to_string_lossy().to_string());
+    }
+
+    let mut groups: Vec<HardLinkGroup> = match target {
+        Some((file_path, info)) => {
+            let group = groups
+                .remove(&(info.device, info.inode))
+                .unwrap_or_else(|| HardLinkGroup { info, paths: vec![file_path] });
+            vec![group]
+        }
+        // a single name under the root is not a duplicate here
+        None => groups.into_values().filter(|group| group.paths.len() > 1).collect(),
+    };
+
+    for group in groups.iter_mut() {
+        group.paths.sort();
+    }
+    groups.sort_by(|a, b| b.paths.len().cmp(&a.paths.len()).then_with(|| a.paths.cmp(&b.paths)));
+    println!("Found {} hard link groups under {:?}", groups.len(), root);
+    Ok(groups)
+}
@@ -0,0 +1,121 @@
+mod commands;
+pub mod search;
+pub mod sch_adv_properties_slot;
+mod front_utils;
+mod custom_category;
+mod cleanup_scan;
+mod search_export;
+mod smart_folder;
+mod log_analyze;
+mod search_cache;
+mod search_diff;
+mod search_refine;
+mod search_stream;
+mod result_store;
+mod background_io;
+mod query_terms;
+mod byte_pattern;
+mod search_history;
+mod autocomplete;
+mod search_suggestion;
+mod content_index;
+mod media_tags;
+mod file_xattr;
+mod hard_links;
+mod scheduled_search;
+mod text_encoding;
+pub mod content_replace;
+pub mod result_sink;
+pub mod search_engine;
+
+use search::AppState;
+use smart_folder::SmartFolderState;
+
+// use search::SearchProcess;
+// use std::sync::Arc;
+
+// runs the app; main.rs only calls this so other binaries (search_cli) can share the modules
+pub fn run() {
+ tauri::Builder::default()
+   .manage(AppState::new())
+   .manage(SmartFolderState::new())
+   .setup(|app| {
+     scheduled_search::start_scheduler(app.handle());
+     Ok(())
+   })
+   .invoke_handler(tauri::generate_handler![
+     commands::get_current_dir,
+     commands::list_files_in_directory,
+     commands::get_file_metadata,
+     commands::create_new_folder,
+     commands::move_to_trash,
+     commands::is_directory,
+     commands::open_file_with_default_program,
+     commands::get_drive_info,
+     commands::read_json_file,
+     commands::save_util_buttons,
+     commands::path_exists,
+     commands::create_new_item,
+     commands::rename_file_or_directory,
+     commands::get_keywords,
+     autocomplete::autocomplete_keywords,
+
+     sch_adv_properties_slot::save_settings,
+     sch_adv_properties_slot::load_settings,
+     sch_adv_properties_slot::delete_settings,
+
+     search::search_files,
+     search::cancel_search,
+     search_suggestion::search_with_suggestion,
+     search_refine::refine_search,
+     search_stream::ack_search_batch,
+
+     search_export::export_search_results,
+
+     content_replace::preview_replace,
+     content_replace::apply_replace,
+     content_replace::rollback_replace,
+     content_replace::list_replace_operations,
+
+     smart_folder::get_smart_folders,
+     smart_folder::create_smart_folder,
+     smart_folder::delete_smart_folder,
+     smart_folder::watch_smart_folder,
+     smart_folder::unwatch_smart_folder,
+
+     scheduled_search::get_scheduled_searches,
+     scheduled_search::save_scheduled_search,
+     scheduled_search::delete_scheduled_search,
+     scheduled_search::run_scheduled_search,
+
+     log_analyze::analyze_search_logs,
+
+     search_history::get_search_history,
+     search_history::pin_search_history,
+     search_history::delete_search_history,
+     search_history::rerun_search_history,
+
+     search_diff::list_search_runs,
+     search_diff::diff_search_runs,
+
+     content_index::build_content_index,
+     content_index::remove_content_index_root,
+     content_index::get_content_index_status,
+     content_index::query_content_index,
+
+     hard_links::find_hard_links,
+
+     front_utils::paste_files,
+     front_utils::move_files_to_trash,
+
+     custom_category::get_categories,
+     custom_category::delete_category,
+     custom_category::add_to_category,
+     custom_category::remove_from_category,
+     custom_category::create_category,
+
+     cleanup_scan::cleanup_scan
+   ])
+   .run(tauri::generate_context!())
+   .expect("error while running tauri application");
+}
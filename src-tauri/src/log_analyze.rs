@@ -0,0 +1,177 @@
+use chrono::{Local, TimeZone};
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use crate::search_history::{self, HistoryEntry};
+
+const DEFAULT_TOP_LIMIT: usize = 10;
+
+#[derive(Serialize, Debug)]
+pub struct DayCount {
+    date: String,
+    count: usize,
+}
+
+#[derive(Serialize, Debug)]
+pub struct DurationStats {
+    key: String,
+    count: usize,
+    average: f64,
+    p50: f64,
+    p90: f64,
+    p99: f64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct QueryRecord {
+    keyword: String,
+    directory: String,
+    method: String,
+    duration: f64,
+    results_count: usize,
+    searched_at: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct KeyCount {
+    key: String,
+    count: usize,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SearchLogStats {
+    total_searches: usize,
+    searches_per_day: Vec<DayCount>,
+    duration_by_method: Vec<DurationStats>,
+    duration_by_root: Vec<DurationStats>,
+    slowest_queries: Vec<QueryRecord>,
+    zero_result_queries: Vec<KeyCount>,
+    top_directories: Vec<KeyCount>,
+}
+
+fn format_time(timestamp: i64, format: &str) -> String {
+    Local.timestamp_opt(timestamp, 0)
+        .single()
+        .map(|time| time.format(format).to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn method_name(method: &str) -> String {
+    match method {
+        "1" => "regex",
+        "2" => "damerau-levenshtein",
+        "3" => "jaccard",
+        "4" => "byte-pattern",
+        "5" => "content-index",
+        _ => "default",
+    }
+    .to_string()
+}
+
+// nearest-rank percentile over sorted durations
+fn percentile(sorted: &[f64], pct: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = ((pct / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+fn duration_stats(groups: HashMap<String, Vec<f64>>) -> Vec<DurationStats> {
+    let mut stats: Vec<DurationStats> = groups
+        .into_iter()
+        .map(|(key, mut durations)| {
+            durations.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+            DurationStats {
+                count: durations.len(),
+                average: durations.iter().sum::<f64>() / durations.len() as f64,
+                p50: percentile(&durations, 50.0),
+                p90: percentile(&durations, 90.0),
+                p99: percentile(&durations, 99.0),
+                key,
+            }
+        })
+        .collect();
+    stats.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.key.cmp(&b.key)));
+    stats
+}
+
+fn top_counts(counts: HashMap<String, usize>, limit: usize) -> Vec<KeyCount> {
+    let mut sorted: Vec<KeyCount> = counts
+        .into_iter()
+        .map(|(key, count)| KeyCount { key, count })
+        .collect();
+    sorted.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.key.cmp(&b.key)));
+    sorted.truncate(limit);
+    sorted
+}
+
+fn analyze(searches: &[HistoryEntry], limit: usize) -> SearchLogStats {
+    let mut per_day: HashMap<String, usize> = HashMap::new();
+    let mut by_method: HashMap<String, Vec<f64>> = HashMap::new();
+    let mut by_root: HashMap<String, Vec<f64>> = HashMap::new();
+    let mut zero_results: HashMap<String, usize> = HashMap::new();
+    let mut directories: HashMap<String, usize> = HashMap::new();
+
+    for search in searches {
+        let date = format_time(search.searched_at, "%Y-%m-%d");
+        *per_day.entry(date).or_insert(0) += 1;
+
+        by_method.entry(method_name(&search.options.custom_sch_method)).or_default().push(search.duration);
+        by_root.entry(search.directory.clone()).or_default().push(search.duration);
+        *directories.entry(search.directory.clone()).or_insert(0) += 1;
+
+        if search.results_count == 0 {
+            *zero_results.entry(search.keyword.clone()).or_insert(0) += 1;
+        }
+    }
+
+    let mut searches_per_day: Vec<DayCount> = per_day
+        .into_iter()
+        .map(|(date, count)| DayCount { date, count })
+        .collect();
+    searches_per_day.sort_by(|a, b| a.date.cmp(&b.date));
+
+    let mut slowest: Vec<&HistoryEntry> = searches.iter().collect();
+    slowest.sort_by(|a, b| b.duration.partial_cmp(&a.duration).unwrap_or(Ordering::Equal));
+    let slowest_queries = slowest
+        .into_iter()
+        .take(limit)
+        .map(|search| QueryRecord {
+            keyword: search.keyword.clone(),
+            directory: search.directory.clone(),
+            method: method_name(&search.options.custom_sch_method),
+            duration: search.duration,
+            results_count: search.results_count,
+            searched_at: format_time(search.searched_at, "%Y-%m-%d %H:%M:%S"),
+        })
+        .collect();
+
+    SearchLogStats {
+        total_searches: searches.len(),
+        searches_per_day,
+        duration_by_method: duration_stats(by_method),
+        duration_by_root: duration_stats(by_root),
+        slowest_queries,
+        zero_result_queries: top_counts(zero_results, limit),
+        top_directories: top_counts(directories, limit),
+    }
+}
+
+/// 검색 로그 통계
+///
+/// 검색 기록 저장소의 검색 로그를 모아 통계 패널용 수치를 계산
+///
+/// # Arguments
+///
+/// * `limit` - 가장 느린 검색, 결과 없는 검색, 자주 검색한 폴더 목록의 최대 길이 (기본 10)
+///
+/// # Returns
+///
+/// * `SearchLogStats` - 일별 검색 수, 검색 방식/폴더별 소요시간, 느린 검색, 결과 없는 검색, 자주 검색한 폴더
+#[tauri::command]
+pub fn analyze_search_logs(limit: Option<usize>) -> SearchLogStats {
+    let searches = search_history::all_entries();
+    analyze(&searches, limit.unwrap_or(DEFAULT_TOP_LIMIT))
+}
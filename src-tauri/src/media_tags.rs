@@ -0,0 +1,489 @@
+use chrono::NaiveDateTime;
+use id3::TagLike;
+use serde::Serialize;
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+const TAKEN_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+// tag blocks bigger than this are skipped (embedded cover art can be huge)
+const MAX_TAG_BLOCK_BYTES: u64 = 16 * 1024 * 1024;
+// how far from the end an Ogg file is read to find its last granule position
+const OGG_TAIL_BYTES: u64 = 64 * 1024;
+
+// photo / music tags of a file; fields the file does not have are None
+#[derive(Serialize, Debug, Default, Clone)]
+pub struct MediaTags {
+    // EXIF DateTimeOriginal, "YYYY-MM-DD HH:MM:SS" (camera local time)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_taken: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub camera_model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_gps: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub artist: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub album: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    // seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<f64>,
+}
+
+impl MediaTags {
+    pub fn taken_at(&self) -> Option<NaiveDateTime> {
+        self.date_taken
+            .as_deref()
+            .and_then(|taken| NaiveDateTime::parse_from_str(taken, TAKEN_FORMAT).ok())
+    }
+}
+
+fn extension_of(path: &Path) -> String {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or_default()
+        .to_lowercase()
+}
+
+// None if the file is not a photo / audio file this reader knows
+pub fn read_media_tags(path: &Path) -> Option<MediaTags> {
+    let tags = match extension_of(path).as_str() {
+        "jpg" | "jpeg" | "tif" | "tiff" | "heic" | "heif" | "png" | "webp" => read_image_tags(path, true),
+        "gif" | "bmp" | "ico" | "psd" => read_image_tags(path, false),
+        "mp3" => read_mp3_tags(path),
+        "flac" => read_flac_tags(path),
+        "ogg" | "oga" | "opus" => read_ogg_tags(path),
+        "m4a" | "m4b" | "mp4" | "m4v" | "mov" => read_mp4_tags(path),
+        _ => return None,
+    };
+    match tags {
+        Ok(tags) => Some(tags),
+        Err(e) => {
+            println!("Failed to read media tags of {:?}: {}", path, e);
+            None
+        }
+    }
+}
+
+fn read_image_tags(path: &Path, has_exif: bool) -> Result<MediaTags, String> {
+    let mut tags = MediaTags::default();
+
+    if let Ok(size) = imagesize::size(path) {
+        tags.width = Some(size.width as u32);
+        tags.height = Some(size.height as u32);
+    }
+
+    if !has_exif {
+        return Ok(tags);
+    }
+    let file = File::open(path).map_err(|e| e.to_string())?;
+    // no EXIF block is normal (screenshots, edited images)
+    let exif = match exif::Reader::new().read_from_container(&mut BufReader::new(file)) {
+        Ok(exif) => exif,
+        Err(_) => return Ok(tags),
+    };
+
+    let taken = exif
+        .get_field(exif::Tag::DateTimeOriginal, exif::In::PRIMARY)
+        .or_else(|| exif.get_field(exif::Tag::DateTime, exif::In::PRIMARY));
+    if let Some(exif::Value::Ascii(values)) = taken.map(|field| &field.value) {
+        if let Some(taken) = values.first().and_then(|value| exif::DateTime::from_ascii(value).ok()) {
+            tags.date_taken = Some(format!(
+                "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+                taken.year, taken.month, taken.day, taken.hour, taken.minute, taken.second
+            ));
+        }
+    }
+
+    if let Some(model) = exif.get_field(exif::Tag::Model, exif::In::PRIMARY) {
+        let model = model.display_value().to_string().trim_matches('"').trim().to_string();
+        if !model.is_empty() {
+            tags.camera_model = Some(model);
+        }
+    }
+
+    tags.has_gps = Some(
+        exif.get_field(exif::Tag::GPSLatitude, exif::In::PRIMARY).is_some()
+            && exif.get_field(exif::Tag::GPSLongitude, exif::In::PRIMARY).is_some(),
+    );
+
+    // EXIF pixel size is used when the image itself could not be measured
+    if tags.width.is_none() {
+        tags.width = exif
+            .get_field(exif::Tag::PixelXDimension, exif::In::PRIMARY)
+            .and_then(|field| field.value.get_uint(0));
+        tags.height = exif
+            .get_field(exif::Tag::PixelYDimension, exif::In::PRIMARY)
+            .and_then(|field| field.value.get_uint(0));
+    }
+    Ok(tags)
+}
+
+fn read_mp3_tags(path: &Path) -> Result<MediaTags, String> {
+    let mut tags = MediaTags::default();
+
+    // ID3v2, falling back to ID3v1
+    let tag = id3::v1v2::read_from_path(path).ok();
+    if let Some(tag) = tag.as_ref() {
+        tags.artist = tag.artist().map(|artist| artist.to_string());
+        tags.album = tag.album().map(|album| album.to_string());
+        tags.title = tag.title().map(|title| title.to_string());
+    }
+
+    // TLEN is often missing or wrong : prefer the stream itself
+    tags.duration = mp3_duration(path)
+        .ok()
+        .flatten()
+        .or_else(|| tag.and_then(|tag| tag.duration()).map(|millis| millis as f64 / 1000.0));
+    Ok(tags)
+}
+
+// duration from the first MPEG audio frame : the Xing / Info frame count if present, else constant bitrate
+fn mp3_duration(path: &Path) -> std::io::Result<Option<f64>> {
+    const BITRATES_V1: [u32; 16] = [0, 32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320, 0];
+    const BITRATES_V2: [u32; 16] = [0, 8, 16, 24, 32, 40, 48, 56, 64, 80, 96, 112, 128, 144, 160, 0];
+    const SAMPLE_RATES_V1: [u32; 3] = [44100, 48000, 32000];
+
+    let mut file = File::open(path)?;
+    let file_len = file.metadata()?.len();
+
+    // skip the ID3v2 tag (size is a 28 bit syncsafe integer)
+    let mut header = [0u8; 10];
+    file.read_exact(&mut header)?;
+    let audio_start = if &header[..3] == b"ID3" {
+        let size = header[6..10].iter().fold(0u64, |size, byte| (size << 7) | (*byte & 0x7F) as u64);
+        10 + size + if header[5] & 0x10 != 0 { 10 } else { 0 }
+    } else {
+        0
+    };
+
+    let mut data = vec![0u8; 64 * 1024];
+    file.seek(SeekFrom::Start(audio_start))?;
+    let read = file.read(&mut data)?;
+    data.truncate(read);
+
+    for start in 0..data.len().saturating_sub(4) {
+        let frame = &data[start..];
+        if frame[0] != 0xFF || frame[1] & 0xE0 != 0xE0 {
+            continue;
+        }
+        let version = (frame[1] >> 3) & 0x03; // 3 : MPEG1, 2 : MPEG2, 0 : MPEG2.5
+        let layer = (frame[1] >> 1) & 0x03; // 1 : Layer III
+        let bitrate_index = (frame[2] >> 4) as usize;
+        let rate_index = ((frame[2] >> 2) & 0x03) as usize;
+        if version == 1 || layer != 1 || rate_index == 3 {
+            continue;
+        }
+
+        let mpeg1 = version == 3;
+        let bitrate = if mpeg1 { BITRATES_V1[bitrate_index] } else { BITRATES_V2[bitrate_index] };
+        let sample_rate = SAMPLE_RATES_V1[rate_index] / match version { 3 => 1, 2 => 2, _ => 4 };
+        if bitrate == 0 {
+            continue;
+        }
+        let samples_per_frame = if mpeg1 { 1152.0 } else { 576.0 };
+        let mono = frame[3] >> 6 == 3;
+
+        // VBR files carry a frame count in their first frame
+        let side_info = match (mpeg1, mono) {
+            (true, false) => 32,
+            (true, true) | (false, false) => 17,
+            (false, true) => 9,
+        };
+        let xing = 4 + side_info;
+        if frame.len() >= xing + 12 && (&frame[xing..xing + 4] == b"Xing" || &frame[xing..xing + 4] == b"Info") {
+            let flags = u32::from_be_bytes([frame[xing + 4], frame[xing + 5], frame[xing + 6], frame[xing + 7]]);
+            if flags & 0x01 != 0 {
+                let frames = u32::from_be_bytes([frame[xing + 8], frame[xing + 9], frame[xing + 10], frame[xing + 11]]);
+                return Ok(Some(frames as f64 * samples_per_frame / sample_rate as f64));
+            }
+        }
+
+        let audio_bytes = file_len.saturating_sub(audio_start + start as u64);
+        return Ok(Some(audio_bytes as f64 * 8.0 / (bitrate as f64 * 1000.0)));
+    }
+    Ok(None)
+}
+
+fn read_u32_le(data: &[u8], at: usize) -> Option<u32> {
+    data.get(at..at + 4).map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+fn read_u32_be(data: &[u8], at: usize) -> Option<u32> {
+    data.get(at..at + 4).map(|bytes| u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+fn read_u64_be(data: &[u8], at: usize) -> Option<u64> {
+    data.get(at..at + 8).map(|bytes| {
+        let mut value = [0u8; 8];
+        value.copy_from_slice(bytes);
+        u64::from_be_bytes(value)
+    })
+}
+
+// Vorbis comment block (FLAC, Ogg Vorbis, Opus) : ARTIST / ALBUM / TITLE
+fn apply_vorbis_comments(data: &[u8], tags: &mut MediaTags) {
+    let vendor_len = match read_u32_le(data, 0) {
+        Some(vendor_len) => vendor_len,
+        None => return,
+    };
+    let mut at = 4 + vendor_len as usize;
+    let count = match read_u32_le(data, at) {
+        Some(count) => count,
+        None => return,
+    };
+    at += 4;
+
+    for _ in 0..count {
+        let len = match read_u32_le(data, at) {
+            Some(len) => len,
+            None => return,
+        };
+        let comment = match data.get(at + 4..at + 4 + len as usize) {
+            Some(comment) => comment,
+            None => return,
+        };
+        at += 4 + len as usize;
+
+        let comment = String::from_utf8_lossy(comment);
+        let (key, value) = match comment.split_once('=') {
+            Some((key, value)) => (key, value),
+            None => continue,
+        };
+        let field = match key.to_uppercase().as_str() {
+            "ARTIST" => &mut tags.artist,
+            "ALBUM" => &mut tags.album,
+            "TITLE" => &mut tags.title,
+            _ => continue,
+        };
+        if field.is_none() {
+            *field = Some(value.to_string());
+        }
+    }
+}
+
+fn read_flac_tags(path: &Path) -> Result<MediaTags, String> {
+    let mut tags = MediaTags::default();
+    let mut file = BufReader::new(File::open(path).map_err(|e| e.to_string())?);
+
+    let mut marker = [0u8; 4];
+    file.read_exact(&mut marker).map_err(|e| e.to_string())?;
+    if &marker != b"fLaC" {
+        return Err("Not a FLAC stream".to_string());
+    }
+
+    loop {
+        let mut header = [0u8; 4];
+        if file.read_exact(&mut header).is_err() {
+            break;
+        }
+        let is_last = header[0] & 0x80 != 0;
+        let block_type = header[0] & 0x7F;
+        let len = u32::from_be_bytes([0, header[1], header[2], header[3]]) as u64;
+
+        match block_type {
+            // STREAMINFO : sample rate (20 bits) and total samples (36 bits)
+            0 | 4 if len <= MAX_TAG_BLOCK_BYTES => {
+                let mut block = vec![0u8; len as usize];
+                file.read_exact(&mut block).map_err(|e| e.to_string())?;
+                if block_type == 0 && block.len() >= 18 {
+                    let sample_rate = ((block[10] as u32) << 12) | ((block[11] as u32) << 4) | ((block[12] as u32) >> 4);
+                    let total_samples = (((block[13] & 0x0F) as u64) << 32)
+                        | u32::from_be_bytes([block[14], block[15], block[16], block[17]]) as u64;
+                    if sample_rate > 0 && total_samples > 0 {
+                        tags.duration = Some(total_samples as f64 / sample_rate as f64);
+                    }
+                } else if block_type == 4 {
+                    apply_vorbis_comments(&block, &mut tags);
+                }
+            }
+            _ => {
+                file.seek(SeekFrom::Current(len as i64)).map_err(|e| e.to_string())?;
+            }
+        }
+        if is_last {
+            break;
+        }
+    }
+    Ok(tags)
+}
+
+// the first `count` packets of the first logical stream
+fn read_ogg_packets(file: &mut BufReader<File>, count: usize) -> Result<Vec<Vec<u8>>, String> {
+    let mut packets = Vec::new();
+    let mut packet = Vec::new();
+
+    while packets.len() < count {
+        let mut header = [0u8; 27];
+        file.read_exact(&mut header).map_err(|e| e.to_string())?;
+        if &header[..4] != b"OggS" {
+            return Err("Not an Ogg stream".to_string());
+        }
+        let mut lacing = vec![0u8; header[26] as usize];
+        file.read_exact(&mut lacing).map_err(|e| e.to_string())?;
+
+        for segment_len in lacing {
+            let mut segment = vec![0u8; segment_len as usize];
+            file.read_exact(&mut segment).map_err(|e| e.to_string())?;
+            packet.extend_from_slice(&segment);
+            if packet.len() as u64 > MAX_TAG_BLOCK_BYTES {
+                return Err("Ogg header packet too large".to_string());
+            }
+            // a segment shorter than 255 bytes ends the packet
+            if segment_len < 255 {
+                packets.push(std::mem::take(&mut packet));
+                if packets.len() == count {
+                    break;
+                }
+            }
+        }
+    }
+    Ok(packets)
+}
+
+// granule position of the last page : total samples (48 kHz for Opus)
+fn ogg_last_granule(file: &mut BufReader<File>) -> Option<u64> {
+    let len = file.seek(SeekFrom::End(0)).ok()?;
+    let start = len.saturating_sub(OGG_TAIL_BYTES);
+    file.seek(SeekFrom::Start(start)).ok()?;
+    let mut tail = Vec::new();
+    file.read_to_end(&mut tail).ok()?;
+
+    let page = tail.windows(4).rposition(|window| window == b"OggS")?;
+    let granule = tail.get(page + 6..page + 14)?;
+    let mut value = [0u8; 8];
+    value.copy_from_slice(granule);
+    Some(u64::from_le_bytes(value))
+}
+
+fn read_ogg_tags(path: &Path) -> Result<MediaTags, String> {
+    let mut tags = MediaTags::default();
+    let mut file = BufReader::new(File::open(path).map_err(|e| e.to_string())?);
+    let packets = read_ogg_packets(&mut file, 2)?;
+    let (identification, comments) = (&packets[0], &packets[1]);
+
+    // Vorbis : sample rate in the identification header; Opus : always 48 kHz minus pre-skip
+    let (sample_rate, pre_skip, comment_data) = if identification.starts_with(b"\x01vorbis") && comments.starts_with(b"\x03vorbis") {
+        (read_u32_le(identification, 12).unwrap_or(0), 0u64, &comments[7..])
+    } else if identification.starts_with(b"OpusHead") && comments.starts_with(b"OpusTags") {
+        let pre_skip = identification.get(10..12).map_or(0, |bytes| u16::from_le_bytes([bytes[0], bytes[1]]) as u64);
+        (48000, pre_skip, &comments[8..])
+    } else {
+        return Err("Unsupported Ogg codec".to_string());
+    };
+    apply_vorbis_comments(comment_data, &mut tags);
+
+    if sample_rate > 0 {
+        if let Some(granule) = ogg_last_granule(&mut file) {
+            tags.duration = Some(granule.saturating_sub(pre_skip) as f64 / sample_rate as f64);
+        }
+    }
+    Ok(tags)
+}
+
+// child boxes of an MP4 box body : (type, body)
+fn mp4_boxes(data: &[u8]) -> Vec<([u8; 4], &[u8])> {
+    let mut boxes = Vec::new();
+    let mut at = 0;
+    while at + 8 <= data.len() {
+        let size = read_u32_be(data, at).unwrap_or(0) as usize;
+        let mut kind = [0u8; 4];
+        kind.copy_from_slice(&data[at + 4..at + 8]);
+        let (header, size) = match size {
+            0 => (8, data.len() - at),
+            1 => (16, read_u64_be(data, at + 8).unwrap_or(0) as usize),
+            size => (8, size),
+        };
+        // a crafted 64-bit size must not overflow
+        let end = match at.checked_add(size) {
+            Some(end) if size >= header && end <= data.len() => end,
+            _ => break,
+        };
+        boxes.push((kind, &data[at + header..end]));
+        at = end;
+    }
+    boxes
+}
+
+fn mp4_child<'a>(data: &'a [u8], kind: &[u8; 4]) -> Option<&'a [u8]> {
+    mp4_boxes(data).into_iter().find(|(child, _)| child == kind).map(|(_, body)| body)
+}
+
+// the "moov" box, found by walking the top-level boxes
+fn read_mp4_moov(path: &Path) -> Result<Vec<u8>, String> {
+    let mut file = BufReader::new(File::open(path).map_err(|e| e.to_string())?);
+    loop {
+        let mut header = [0u8; 8];
+        file.read_exact(&mut header).map_err(|_| "No moov box".to_string())?;
+        let mut size = read_u32_be(&header, 0).unwrap_or(0) as u64;
+        let mut header_len = 8;
+        if size == 1 {
+            let mut large = [0u8; 8];
+            file.read_exact(&mut large).map_err(|e| e.to_string())?;
+            size = u64::from_be_bytes(large);
+            header_len = 16;
+        }
+        if size == 0 || size < header_len {
+            return Err("No moov box".to_string());
+        }
+
+        let body_len = size - header_len;
+        if &header[4..8] == b"moov" {
+            if body_len > MAX_TAG_BLOCK_BYTES * 4 {
+                return Err("moov box too large".to_string());
+            }
+            let mut body = vec![0u8; body_len as usize];
+            file.read_exact(&mut body).map_err(|e| e.to_string())?;
+            return Ok(body);
+        }
+        let skip = i64::try_from(body_len).map_err(|_| "Invalid mp4 box size".to_string())?;
+        file.seek(SeekFrom::Current(skip)).map_err(|e| e.to_string())?;
+    }
+}
+
+fn read_mp4_tags(path: &Path) -> Result<MediaTags, String> {
+    let mut tags = MediaTags::default();
+    let moov = read_mp4_moov(path)?;
+
+    // mvhd : version 0 has 32 bit times, version 1 64 bit
+    if let Some(mvhd) = mp4_child(&moov, b"mvhd") {
+        let (timescale, duration) = match mvhd.first() {
+            Some(1) => (read_u32_be(mvhd, 20), read_u64_be(mvhd, 24)),
+            _ => (read_u32_be(mvhd, 12), read_u32_be(mvhd, 16).map(|duration| duration as u64)),
+        };
+        if let (Some(timescale), Some(duration)) = (timescale, duration) {
+            if timescale > 0 {
+                tags.duration = Some(duration as f64 / timescale as f64);
+            }
+        }
+    }
+
+    // iTunes metadata : moov/udta/meta/ilst/<©ART|©alb|©nam>/data
+    let meta = mp4_child(&moov, b"udta").and_then(|udta| mp4_child(udta, b"meta"));
+    if let Some(meta) = meta {
+        // "meta" is a full box (4 bytes version / flags) except in some QuickTime files
+        let meta = if meta.get(4..8) == Some(b"hdlr") { meta } else { meta.get(4..).unwrap_or_default() };
+        if let Some(ilst) = mp4_child(meta, b"ilst") {
+            for (kind, item) in mp4_boxes(ilst) {
+                let field = match &kind {
+                    b"\xa9ART" => &mut tags.artist,
+                    b"\xa9alb" => &mut tags.album,
+                    b"\xa9nam" => &mut tags.title,
+                    _ => continue,
+                };
+                // data box : type (4) + locale (4) + UTF-8 text
+                if let Some(text) = mp4_child(item, b"data").and_then(|data| data.get(8..)) {
+                    *field = Some(String::from_utf8_lossy(text).to_string());
+                }
+            }
+        }
+    }
+    Ok(tags)
+}
@@ -0,0 +1,105 @@
+use crate::search::SearchOptions;
+
+// one word of a multi-term query
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryTerm {
+    pub text: String,
+    // "-word" : the term must NOT appear
+    pub exclude: bool,
+}
+
+// where the terms are looked for; "name" is the match target (matchTarget)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TermFields {
+    pub name: bool,
+    pub path: bool,
+    pub content: bool,
+}
+
+impl TermFields {
+    // termFields "name,path,content"; empty : the name, plus the content if customFileContUse
+    pub fn from_options(options: &SearchOptions) -> Self {
+        let list = options.term_fields.trim();
+        if list.is_empty() {
+            return TermFields {
+                name: true,
+                path: false,
+                content: options.custom_file_cont_use,
+            };
+        }
+
+        let mut fields = TermFields { name: false, path: false, content: false };
+        for field in list.split(',').map(|field| field.trim()) {
+            match field {
+                "name" => fields.name = true,
+                "path" => fields.path = true,
+                "content" => fields.content = true,
+                _ => println!("Unknown term field: {}", field),
+            }
+        }
+        fields
+    }
+}
+
+// `invoice "acme corp" -draft` -> invoice, acme corp, not draft
+pub fn parse_query(query: &str) -> Vec<QueryTerm> {
+    let mut terms = Vec::new();
+    let mut chars = query.chars().peekable();
+
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        let first = match chars.peek() {
+            Some(&first) => first,
+            None => break,
+        };
+
+        let mut exclude = false;
+        if first == '-' {
+            chars.next();
+            match chars.peek() {
+                Some(c) if !c.is_whitespace() => exclude = true,
+                // a lone "-" is searched as it is
+                _ => {
+                    terms.push(QueryTerm { text: "-".to_string(), exclude: false });
+                    continue;
+                }
+            }
+        }
+
+        let mut text = String::new();
+        if chars.peek() == Some(&'"') {
+            // quoted phrase : kept literally, spaces included, up to the closing quote
+            chars.next();
+            for c in chars.by_ref() {
+                if c == '"' {
+                    break;
+                }
+                text.push(c);
+            }
+        } else {
+            while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+                text.push(c);
+            }
+        }
+
+        if !text.is_empty() {
+            terms.push(QueryTerm { text, exclude });
+        }
+    }
+    terms
+}
+
+// every term is found (in any field, any order) and no excluded term is; unsearched fields are None
+pub fn matches_all(terms: &[QueryTerm], name: Option<&str>, path: Option<&str>, content: Option<&str>) -> bool {
+    let has_required = terms.iter().any(|term| !term.exclude);
+    if !has_required {
+        return false;
+    }
+
+    terms.iter().all(|term| {
+        let found = name.map_or(false, |name| name.contains(&term.text))
+            || path.map_or(false, |path| path.contains(&term.text))
+            || content.map_or(false, |content| content.contains(&term.text));
+        found != term.exclude
+    })
+}
@@ -0,0 +1,115 @@
+use std::fs::{self, File};
+use std::future::Future;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::Mutex;
+use tauri::Window;
+use tokio::sync::mpsc::Sender;
+
+use crate::search::FileItem;
+
+pub type SinkFuture<'a> = Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>>;
+
+// where the search engine puts its matches; an error stops the search
+pub trait ResultSink: Send + Sync {
+    fn send(&self, file_item: FileItem) -> SinkFuture<'_>;
+}
+
+// to a channel, for a consumer that batches / stores the results (search_files)
+pub struct ChannelSink {
+    tx: Sender<FileItem>,
+}
+
+impl ChannelSink {
+    pub fn new(tx: Sender<FileItem>) -> Self {
+        ChannelSink { tx }
+    }
+}
+
+impl ResultSink for ChannelSink {
+    fn send(&self, file_item: FileItem) -> SinkFuture<'_> {
+        Box::pin(async move { self.tx.send(file_item).await.map_err(|e| e.to_string()) })
+    }
+}
+
+// one window event per match
+pub struct WindowSink {
+    window: Window,
+    event: String,
+}
+
+impl WindowSink {
+    pub fn new(window: Window, event: &str) -> Self {
+        WindowSink { window, event: event.to_string() }
+    }
+}
+
+impl ResultSink for WindowSink {
+    fn send(&self, file_item: FileItem) -> SinkFuture<'_> {
+        Box::pin(async move { self.window.emit(&self.event, file_item).map_err(|e| e.to_string()) })
+    }
+}
+
+// one JSON line per match
+pub struct FileSink {
+    writer: Mutex<BufWriter<File>>,
+}
+
+impl FileSink {
+    pub fn create(path: &Path) -> Result<Self, String> {
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let file = File::create(path).map_err(|e| e.to_string())?;
+        Ok(FileSink { writer: Mutex::new(BufWriter::new(file)) })
+    }
+
+    pub fn flush(&self) -> Result<(), String> {
+        let mut writer = self.writer.lock().map_err(|e| e.to_string())?;
+        writer.flush().map_err(|e| e.to_string())
+    }
+}
+
+impl ResultSink for FileSink {
+    fn send(&self, file_item: FileItem) -> SinkFuture<'_> {
+        Box::pin(async move {
+            let line = serde_json::to_string(&file_item).map_err(|e| e.to_string())?;
+            let mut writer = self.writer.lock().map_err(|e| e.to_string())?;
+            writeln!(writer, "{}", line).map_err(|e| e.to_string())
+        })
+    }
+}
+
+impl Drop for FileSink {
+    fn drop(&mut self) {
+        if let Err(e) = self.flush() {
+            println!("Failed to flush result file: {}", e);
+        }
+    }
+}
+
+// kept in memory, in the order they were found
+#[derive(Default)]
+pub struct CollectorSink {
+    items: Mutex<Vec<FileItem>>,
+}
+
+impl CollectorSink {
+    pub fn new() -> Self {
+        CollectorSink::default()
+    }
+
+    pub fn take(&self) -> Vec<FileItem> {
+        self.items.lock().map(|mut items| std::mem::take(&mut *items)).unwrap_or_default()
+    }
+}
+
+impl ResultSink for CollectorSink {
+    fn send(&self, file_item: FileItem) -> SinkFuture<'_> {
+        Box::pin(async move {
+            self.items.lock().map_err(|e| e.to_string())?.push(file_item);
+            Ok(())
+        })
+    }
+}
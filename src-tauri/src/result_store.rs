@@ -0,0 +1,148 @@
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::PathBuf;
+use std::sync::Once;
+
+use crate::search::FileItem;
+
+const SPILL_DIR: &str = "../backend_properties/search_spill";
+const RESULT_STORE_PROPERTIES_PATH: &str = "../backend_properties/search_properties/search_state_properties.json";
+const DEFAULT_RESULT_MEMORY_LIMIT: usize = 100_000;
+
+// spill files left over by a crash are removed before the first store is created
+static SPILL_CLEANUP: Once = Once::new();
+
+#[derive(Deserialize, Debug)]
+struct ResultStoreProperties {
+    // results kept in memory per search; the rest go to a spill file
+    #[serde(rename = "resultMemoryLimit", default = "default_result_memory_limit")]
+    result_memory_limit: usize,
+}
+
+fn default_result_memory_limit() -> usize {
+    DEFAULT_RESULT_MEMORY_LIMIT
+}
+
+fn read_result_memory_limit() -> usize {
+    fs::read_to_string(RESULT_STORE_PROPERTIES_PATH)
+        .ok()
+        .and_then(|data| serde_json::from_str::<ResultStoreProperties>(&data).ok())
+        .map(|properties| properties.result_memory_limit)
+        .unwrap_or(DEFAULT_RESULT_MEMORY_LIMIT)
+}
+
+// results of one search : the first `memory_limit` in memory, the overflow as JSON lines on disk
+pub struct ResultStore {
+    memory: Vec<FileItem>,
+    memory_limit: usize,
+    // paths already stored (only the paths stay in memory for spilled results)
+    seen: HashSet<String>,
+    spill_path: PathBuf,
+    spill: Option<BufWriter<File>>,
+    spilled: usize,
+}
+
+impl ResultStore {
+    pub fn new(process_id: &str) -> Self {
+        SPILL_CLEANUP.call_once(|| {
+            let _ = fs::remove_dir_all(SPILL_DIR);
+        });
+
+        ResultStore {
+            memory: Vec::new(),
+            memory_limit: read_result_memory_limit(),
+            seen: HashSet::new(),
+            spill_path: PathBuf::from(SPILL_DIR).join(format!("{}.jsonl", process_id)),
+            spill: None,
+            spilled: 0,
+        }
+    }
+
+    // false if the path was already stored
+    pub fn push(&mut self, file_item: FileItem) -> bool {
+        if !self.seen.insert(file_item.file_path.clone()) {
+            return false;
+        }
+
+        if self.memory.len() < self.memory_limit {
+            self.memory.push(file_item);
+            return true;
+        }
+
+        match self.spill_writer() {
+            Some(writer) => {
+                let written = serde_json::to_string(&file_item)
+                    .map_err(|e| e.to_string())
+                    .and_then(|line| writeln!(writer, "{}", line).map_err(|e| e.to_string()));
+                match written {
+                    Ok(()) => self.spilled += 1,
+                    Err(e) => println!("Failed to spill search result: {}", e),
+                }
+            }
+            // no spill file : keep it in memory rather than lose it
+            None => self.memory.push(file_item),
+        }
+        true
+    }
+
+    fn spill_writer(&mut self) -> Option<&mut BufWriter<File>> {
+        if self.spill.is_none() {
+            let opened = fs::create_dir_all(SPILL_DIR).and_then(|_| File::create(&self.spill_path));
+            match opened {
+                Ok(file) => {
+                    println!("Search results over {} spill to {:?}", self.memory_limit, self.spill_path);
+                    self.spill = Some(BufWriter::new(file));
+                }
+                Err(e) => println!("Failed to create spill file: {}", e),
+            }
+        }
+        self.spill.as_mut()
+    }
+
+    pub fn len(&self) -> usize {
+        self.memory.len() + self.spilled
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // flush the spill file; call once the search is done, before reading
+    pub fn finish(&mut self) {
+        if let Some(writer) = self.spill.as_mut() {
+            if let Err(e) = writer.flush() {
+                println!("Failed to flush spill file: {}", e);
+            }
+        }
+    }
+
+    // every result in insertion order, reading the spilled part from disk
+    pub fn items(&self) -> impl Iterator<Item = FileItem> + '_ {
+        let spilled = if self.spilled > 0 {
+            File::open(&self.spill_path).ok()
+        } else {
+            None
+        };
+        let spilled_items = spilled.into_iter().flat_map(|file| {
+            BufReader::new(file)
+                .lines()
+                .map_while(Result::ok)
+                .filter_map(|line| serde_json::from_str::<FileItem>(&line).ok())
+        });
+        self.memory.iter().cloned().chain(spilled_items)
+    }
+
+    pub fn paths(&self) -> impl Iterator<Item = String> + '_ {
+        self.items().map(|file_item| file_item.file_path)
+    }
+}
+
+impl Drop for ResultStore {
+    fn drop(&mut self) {
+        if self.spill.take().is_some() {
+            let _ = fs::remove_file(&self.spill_path);
+        }
+    }
+}
@@ -23,8 +23,58 @@ pub struct SearchSettings {
     modiEnd: String,
     ownerName: String,
     fileTypeList: String,
+    #[serde(default)]
+    uniqueInodeUse: bool,
     customSymbolicChk: bool,
+    #[serde(default)]
+    customMediaTagUse: bool,
+    #[serde(default)]
+    takenStart: String,
+    #[serde(default)]
+    takenEnd: String,
+    #[serde(default)]
+    cameraModel: String,
+    #[serde(default)]
+    gpsFilter: String,
+    #[serde(default)]
+    widthMin: u32,
+    #[serde(default)]
+    widthMax: u32,
+    #[serde(default)]
+    heightMin: u32,
+    #[serde(default)]
+    heightMax: u32,
+    #[serde(default)]
+    artist: String,
+    #[serde(default)]
+    album: String,
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    durationMin: f64,
+    #[serde(default)]
+    durationMax: f64,
+    #[serde(default)]
+    customXattrUse: bool,
+    #[serde(default)]
+    xattrName: String,
+    #[serde(default)]
+    xattrMatch: String,
+    #[serde(default)]
+    xattrValue: String,
+    #[serde(default)]
+    symbolicMode: String,
     customSchMethod: String,
+    #[serde(default)]
+    matchTarget: String,
+    #[serde(default)]
+    multiTermUse: bool,
+    #[serde(default)]
+    termFields: String,
+    #[serde(default)]
+    emitMode: String,
+    #[serde(default)]
+    priority: String,
     customLogUse: bool,
     fileMaxRawVal : u64,
     fileMinRawVal : u64,
@@ -32,6 +82,63 @@ pub struct SearchSettings {
     fileMinUnit : String,
 }
 
+impl Default for SearchSettings {
+    fn default() -> Self {
+        SearchSettings {
+            customThreadPoolUse: false,
+            threadPoolNum: "0".to_string(),
+            searchScope: "0".to_string(),
+            customFileContUse: false,
+            customPropertyUse: false,
+            customFileSizeUse: false,
+            customFileCrtDateUse: false,
+            customFileModiDateUse: false,
+            customFileOwnerUse: false,
+            customFileTypeUse: false,
+            sizeMax: 0,
+            sizeMin: 0,
+            crtStart: "".to_string(),
+            crtEnd: "".to_string(),
+            modiStart: "".to_string(),
+            modiEnd: "".to_string(),
+            ownerName: "".to_string(),
+            fileTypeList: "".to_string(),
+            uniqueInodeUse: false,
+            customSymbolicChk: false,
+            customMediaTagUse: false,
+            takenStart: "".to_string(),
+            takenEnd: "".to_string(),
+            cameraModel: "".to_string(),
+            gpsFilter: "0".to_string(),
+            widthMin: 0,
+            widthMax: 0,
+            heightMin: 0,
+            heightMax: 0,
+            artist: "".to_string(),
+            album: "".to_string(),
+            title: "".to_string(),
+            durationMin: 0.0,
+            durationMax: 0.0,
+            customXattrUse: false,
+            xattrName: "".to_string(),
+            xattrMatch: "0".to_string(),
+            xattrValue: "".to_string(),
+            symbolicMode: "0".to_string(),
+            customSchMethod: "0".to_string(),
+            matchTarget: "0".to_string(),
+            multiTermUse: false,
+            termFields: "".to_string(),
+            emitMode: "0".to_string(),
+            priority: "0".to_string(),
+            customLogUse: false,
+            fileMaxRawVal : 0,
+            fileMinRawVal : 0,
+            fileMaxUnit : "B".to_string(),
+            fileMinUnit : "B".to_string(),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct SettingsData {
     data: Vec<SettingSlot>,
@@ -56,33 +163,7 @@ pub fn read_settings_file() -> SettingsData {
                 .map(|i| SettingSlot {
                     number: i,
                     name: String::new(),
-                    val: SearchSettings {
-                        customThreadPoolUse: false,
-                        threadPoolNum: "0".to_string(),
-                        searchScope: "0".to_string(),
-                        customFileContUse: false,
-                        customPropertyUse: false,
-                            customFileSizeUse: false,
-                            customFileCrtDateUse: false,
-                            customFileModiDateUse: false,
-                            customFileOwnerUse: false,
-                            customFileTypeUse:false,
-                        sizeMax: 0,
-                        sizeMin: 0,
-                        crtStart: "".to_string(),
-                        crtEnd: "".to_string(),
-                        modiStart: "".to_string(),
-                        modiEnd: "".to_string(),
-                        ownerName: "".to_string(),
-                        fileTypeList: "".to_string(),
-                        customSymbolicChk: false,
-                        customSchMethod: "0".to_string(),
-                        customLogUse: false,
-                        fileMaxRawVal : 0,
-                        fileMinRawVal : 0,
-                        fileMaxUnit : "B".to_string(),
-                        fileMinUnit : "B".to_string(),
-                    },
+                    val: SearchSettings::default(),
                 })
                 .collect(),
         };
@@ -98,6 +179,18 @@ pub fn read_settings_file() -> SettingsData {
 }
 
 
+// settings saved in a slot, without creating the settings file (search_cli)
+pub fn saved_slot_settings(slot_number: u32) -> Result<SearchSettings, String> {
+    let content = std::fs::read_to_string(SETTINGS_FILE)
+        .map_err(|e| format!("Failed to read {}: {}", SETTINGS_FILE, e))?;
+    let data: SettingsData = serde_json::from_str(&content).map_err(|e| e.to_string())?;
+    data.data
+        .into_iter()
+        .find(|slot| slot.number == slot_number)
+        .map(|slot| slot.val)
+        .ok_or_else(|| format!("No settings slot {}", slot_number))
+}
+
 fn write_settings_file(data: &SettingsData) {
     let mut file = OpenOptions::new()
         .write(true)
@@ -134,33 +227,7 @@ pub fn delete_settings(slot_number: u32) -> String {
 
     if let Some(slot) = data.data.iter_mut().find(|s| s.number == slot_number) {
         slot.name = String::new();
-        slot.val = SearchSettings {
-            customThreadPoolUse: false,
-            threadPoolNum: "0".to_string(),
-            searchScope: "0".to_string(),
-            customFileContUse: false,
-            customPropertyUse: false,
-                customFileSizeUse: false,
-                customFileCrtDateUse: false,
-                customFileModiDateUse: false,
-                customFileOwnerUse: false,
-                customFileTypeUse:false,
-            sizeMax: 0,
-            sizeMin: 0,
-            crtStart: "".to_string(),
-            crtEnd: "".to_string(),
-            modiStart: "".to_string(),
-            modiEnd: "".to_string(),
-            ownerName: "".to_string(),
-            fileTypeList: "".to_string(),
-            customSymbolicChk: false,
-            customSchMethod: "0".to_string(),
-            customLogUse: false,
-            fileMaxRawVal : 0,
-            fileMinRawVal : 0,
-            fileMaxUnit : "B".to_string(),
-            fileMinUnit : "B".to_string(),
-        };
+        slot.val = SearchSettings::default();
     }
 
     write_settings_file(&data);
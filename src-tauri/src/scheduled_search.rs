@@ -0,0 +1,378 @@
+use chrono::Local;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+use crate::background_io;
+use crate::sch_adv_properties_slot::SearchSettings;
+use crate::search::{collect_search_results, SearchOptions};
+use crate::search_diff;
+
+const SCHEDULED_FILE: &str = "../src/properties/sch_scheduled_set.json";
+
+// how often the scheduler looks for searches that are due
+const SCHEDULER_TICK_SECS: u64 = 30;
+const MIN_INTERVAL_MINUTES: u64 = 1;
+// one year
+const MAX_INTERVAL_MINUTES: u64 = 365 * 24 * 60;
+
+// serializes read-modify-write of the schedule file (scheduler vs commands)
+static SCHEDULE_FILE_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+// ids of the searches running right now, so "run now" and the scheduler do not overlap
+static RUNNING: Lazy<Mutex<HashSet<String>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ScheduledSearch {
+    id: String,
+    name: String,
+    keyword: String,
+    roots: Vec<String>,
+    // same settings object as the slots in sch_adv_properties_slot
+    options: SearchSettings,
+    interval_minutes: u64,
+    enabled: bool,
+    // unix timestamp (seconds) of the last finished run
+    #[serde(default)]
+    last_run_at: Option<i64>,
+    #[serde(default)]
+    last_results_count: usize,
+    // stored runs of the last run, one per root (see search_diff::diff_search_runs)
+    #[serde(default)]
+    last_run_ids: Vec<String>,
+    #[serde(default)]
+    last_error: Option<String>,
+}
+
+impl ScheduledSearch {
+    fn is_due(&self, now: i64) -> bool {
+        let interval_secs = i64::try_from(self.interval_minutes.saturating_mul(60)).unwrap_or(i64::MAX);
+        self.enabled && now.saturating_sub(self.last_run_at.unwrap_or(0)) >= interval_secs
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct ScheduledSearchData {
+    data: Vec<ScheduledSearch>,
+}
+
+// payload of the "scheduled-search-update" event, against the previous run of every root
+#[derive(Serialize, Debug, Clone)]
+pub struct ScheduledSearchUpdate {
+    id: String,
+    name: String,
+    ran_at: i64,
+    results_count: usize,
+    added: Vec<String>,
+    removed: Vec<String>,
+    modified: Vec<String>,
+}
+
+fn read_scheduled_file() -> ScheduledSearchData {
+    let data = fs::read_to_string(SCHEDULED_FILE).unwrap_or_default();
+    serde_json::from_str(&data).unwrap_or_default()
+}
+
+fn write_scheduled_file(data: &ScheduledSearchData) -> Result<(), String> {
+    if let Some(parent) = Path::new(SCHEDULED_FILE).parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let serialized_data = serde_json::to_string_pretty(data).map_err(|e| e.to_string())?;
+    fs::write(SCHEDULED_FILE, serialized_data).map_err(|e| e.to_string())
+}
+
+fn find_scheduled_search(id: &str) -> Option<ScheduledSearch> {
+    let _guard = SCHEDULE_FILE_LOCK.lock().ok()?;
+    read_scheduled_file().data.into_iter().find(|schedule| schedule.id == id)
+}
+
+// slot settings -> engine options; scheduled runs always use background priority
+fn search_options(settings: &SearchSettings) -> Result<SearchOptions, String> {
+    let value = serde_json::to_value(settings).map_err(|e| e.to_string())?;
+    let mut options: SearchOptions = serde_json::from_value(value).map_err(|e| e.to_string())?;
+    options.priority = background_io::PRIORITY_BACKGROUND.to_string();
+    Ok(options)
+}
+
+// run every root, store the results as search runs and diff them against the runs of the last run
+// the first run of a root has nothing to compare with : it only records the results
+async fn run_scheduled(schedule: &ScheduledSearch) -> Result<ScheduledSearchUpdate, String> {
+    let options = search_options(&schedule.options)?;
+    let mut update = ScheduledSearchUpdate {
+        id: schedule.id.clone(),
+        name: schedule.name.clone(),
+        ran_at: Local::now().timestamp(),
+        results_count: 0,
+        added: vec![],
+        removed: vec![],
+        modified: vec![],
+    };
+
+    // runs of this schedule only : interactive searches of the same query store their own
+    let last_run_ids = schedule.last_run_ids.clone();
+    let previous_runs = background_io::spawn_blocking(true, move || {
+        last_run_ids.iter().filter_map(|id| search_diff::load_run(id)).collect::<Vec<_>>()
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let mut run_ids = Vec::new();
+    let mut errors = Vec::new();
+
+    for root in &schedule.roots {
+        let previous = previous_runs
+            .iter()
+            .find(|run| run.is_run_of(&schedule.keyword, root, &options));
+        let items = match collect_search_results(PathBuf::from(root), schedule.keyword.clone(), options.clone()).await {
+            Ok(items) => items,
+            Err(e) => {
+                println!("Scheduled search {} failed for {}: {}", schedule.id, root, e);
+                errors.push(format!("{}: {}", root, e));
+                // the next run diffs against the last one that worked
+                if let Some(previous) = previous {
+                    run_ids.push(previous.id.clone());
+                }
+                continue;
+            }
+        };
+        update.results_count += items.len();
+
+        // stats of every result and the run files : off the async workers
+        let (keyword, directory, run_options) = (schedule.keyword.clone(), root.clone(), options.clone());
+        let run = background_io::spawn_blocking(true, move || {
+            search_diff::save_scheduled_run(&keyword, &directory, &run_options, items.into_iter().map(|item| item.file_path))
+        })
+        .await
+        .map_err(|e| e.to_string())??;
+        if let Some(previous) = previous {
+            let diff = search_diff::diff_against_run(None, previous, &run);
+            update.added.extend(diff.added);
+            update.removed.extend(diff.removed);
+            update.modified.extend(diff.modified);
+        }
+        run_ids.push(run.id);
+    }
+
+    // remember the run even if some roots failed, so a missing folder does not retry every tick
+    let stale_run_ids = {
+        let _guard = SCHEDULE_FILE_LOCK.lock().map_err(|e| e.to_string())?;
+        let mut data = read_scheduled_file();
+        let stale_run_ids = match data.data.iter_mut().find(|stored| stored.id == schedule.id) {
+            Some(stored) => {
+                stored.last_run_at = Some(update.ran_at);
+                stored.last_results_count = update.results_count;
+                stored.last_error = (!errors.is_empty()).then(|| errors.join("\n"));
+                let previous_ids = std::mem::replace(&mut stored.last_run_ids, run_ids.clone());
+                previous_ids.into_iter().filter(|id| !run_ids.contains(id)).collect()
+            }
+            // deleted while running
+            None => run_ids,
+        };
+        write_scheduled_file(&data)?;
+        stale_run_ids
+    };
+    remove_scheduled_runs(stale_run_ids);
+
+    if errors.len() == schedule.roots.len() {
+        return Err(errors.join("\n"));
+    }
+    Ok(update)
+}
+
+// runs of a schedule are not dropped by the search_runs limits, so the schedule removes the ones it no longer needs
+fn remove_scheduled_runs(ids: Vec<String>) {
+    if ids.is_empty() {
+        return;
+    }
+    background_io::spawn_blocking(true, move || {
+        if let Err(e) = search_diff::remove_runs(&ids) {
+            println!("Failed to remove scheduled search runs: {}", e);
+        }
+    });
+}
+
+// run unless it is already running; emits "scheduled-search-update" when new files match
+async fn run_and_notify(app: &AppHandle, schedule: &ScheduledSearch) -> Result<ScheduledSearchUpdate, String> {
+    if !RUNNING.lock().map_err(|e| e.to_string())?.insert(schedule.id.clone()) {
+        return Err(format!("Scheduled search is already running: {}", schedule.name));
+    }
+    println!("Running scheduled search {} ({})", schedule.name, schedule.id);
+    let result = run_scheduled(schedule).await;
+    if let Ok(mut running) = RUNNING.lock() {
+        running.remove(&schedule.id);
+    }
+
+    let update = result?;
+    println!(
+        "Scheduled search {} : {} results, {} new",
+        schedule.id,
+        update.results_count,
+        update.added.len()
+    );
+    if !update.added.is_empty() {
+        if let Err(e) = app.emit_all("scheduled-search-update", update.clone()) {
+            println!("Failed to emit scheduled search update: {:?}", e);
+        }
+    }
+    Ok(update)
+}
+
+// started once from the app setup; runs the due searches one after another while the app runs
+pub fn start_scheduler(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(tokio::time::Duration::from_secs(SCHEDULER_TICK_SECS)).await;
+
+            let now = Local::now().timestamp();
+            let due: Vec<ScheduledSearch> = match SCHEDULE_FILE_LOCK.lock() {
+                Ok(_guard) => read_scheduled_file().data.into_iter().filter(|schedule| schedule.is_due(now)).collect(),
+                Err(_) => continue,
+            };
+            for schedule in due {
+                if let Err(e) = run_and_notify(&app, &schedule).await {
+                    println!("Scheduled search {} failed: {}", schedule.id, e);
+                }
+            }
+        }
+    });
+}
+
+/// 예약 검색 목록
+///
+/// # Returns
+///
+/// * `Vec<ScheduledSearch>` - 저장된 예약 검색 (마지막 실행 정보 포함)
+#[tauri::command]
+pub fn get_scheduled_searches() -> Vec<ScheduledSearch> {
+    let _guard = SCHEDULE_FILE_LOCK.lock();
+    read_scheduled_file().data
+}
+
+/// 예약 검색 저장
+///
+/// 앱이 실행 중인 동안 주기적으로 다시 검색하고, 새로 일치하는 파일이 있으면 "scheduled-search-update" 이벤트를 보냄
+///
+/// # Arguments
+///
+/// * `id` - 수정할 예약 검색 id (없으면 새로 만듦)
+/// * `name` - 이름
+/// * `keyword` - 검색어
+/// * `roots` - 검색할 폴더들
+/// * `options` - 검색 옵션 (슬롯 설정과 같은 형식)
+/// * `interval_minutes` - 실행 간격 (분, 최소 1, 최대 1년)
+/// * `enabled` - 예약 실행 여부
+///
+/// # Returns
+///
+/// * `Result<ScheduledSearch, String>` - 저장된 예약 검색
+#[tauri::command]
+pub fn save_scheduled_search(
+    id: Option<String>,
+    name: String,
+    keyword: String,
+    roots: Vec<String>,
+    options: SearchSettings,
+    interval_minutes: u64,
+    enabled: bool,
+) -> Result<ScheduledSearch, String> {
+    if roots.is_empty() {
+        return Err("At least one root folder is required".to_string());
+    }
+    if let Some(missing) = roots.iter().find(|root| !Path::new(root).is_dir()) {
+        return Err(format!("Directory does not exist: {}", missing));
+    }
+    if interval_minutes < MIN_INTERVAL_MINUTES {
+        return Err(format!("Interval must be at least {} minute(s)", MIN_INTERVAL_MINUTES));
+    }
+    if interval_minutes > MAX_INTERVAL_MINUTES {
+        return Err(format!("Interval must be at most {} minutes", MAX_INTERVAL_MINUTES));
+    }
+    search_options(&options)?;
+
+    let _guard = SCHEDULE_FILE_LOCK.lock().map_err(|e| e.to_string())?;
+    let mut data = read_scheduled_file();
+    let schedule = match id {
+        Some(id) => {
+            let stored = data
+                .data
+                .iter_mut()
+                .find(|stored| stored.id == id)
+                .ok_or_else(|| format!("Scheduled search does not exist: {}", id))?;
+            stored.name = name;
+            stored.keyword = keyword;
+            stored.roots = roots;
+            stored.options = options;
+            stored.interval_minutes = interval_minutes;
+            stored.enabled = enabled;
+            stored.clone()
+        }
+        None => {
+            let schedule = ScheduledSearch {
+                id: uuid::Uuid::new_v4().to_string(),
+                name,
+                keyword,
+                roots,
+                options,
+                interval_minutes,
+                enabled,
+                last_run_at: None,
+                last_results_count: 0,
+                last_run_ids: vec![],
+                last_error: None,
+            };
+            data.data.push(schedule.clone());
+            schedule
+        }
+    };
+
+    write_scheduled_file(&data)?;
+    Ok(schedule)
+}
+
+/// 예약 검색 삭제
+///
+/// 이 예약 검색이 저장한 검색 결과(search_runs)도 함께 삭제
+///
+/// # Arguments
+///
+/// * `id` - 예약 검색 id
+///
+/// # Returns
+///
+/// * `Result<Vec<ScheduledSearch>, String>` - 남은 예약 검색 목록
+#[tauri::command]
+pub fn delete_scheduled_search(id: String) -> Result<Vec<ScheduledSearch>, String> {
+    let _guard = SCHEDULE_FILE_LOCK.lock().map_err(|e| e.to_string())?;
+    let mut data = read_scheduled_file();
+    let removed_run_ids: Vec<String> = data
+        .data
+        .iter()
+        .filter(|schedule| schedule.id == id)
+        .flat_map(|schedule| schedule.last_run_ids.clone())
+        .collect();
+    data.data.retain(|schedule| schedule.id != id);
+    write_scheduled_file(&data)?;
+    remove_scheduled_runs(removed_run_ids);
+    Ok(data.data)
+}
+
+/// 예약 검색 바로 실행
+///
+/// 예약 시간과 관계없이 지금 실행 (다음 예약 실행은 이 시점부터 다시 계산)
+///
+/// # Arguments
+///
+/// * `id` - 예약 검색 id
+///
+/// # Returns
+///
+/// * `Result<ScheduledSearchUpdate, String>` - 결과 수와 이전 실행 대비 추가 / 삭제 / 수정된 파일
+#[tauri::command]
+pub async fn run_scheduled_search(app: AppHandle, id: String) -> Result<ScheduledSearchUpdate, String> {
+    let schedule = find_scheduled_search(&id).ok_or_else(|| format!("Scheduled search does not exist: {}", id))?;
+    run_and_notify(&app, &schedule).await
+}
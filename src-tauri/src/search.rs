@@ -2,15 +2,11 @@
 
 use std::fs;
 use std::path::{Path, PathBuf};
-use tokio::fs as async_fs;
 use std::sync::{Arc};
-use tokio::sync::{Mutex, mpsc::{self, Sender}};
+use tokio::sync::{Mutex, mpsc};
 use serde::Deserialize;
-use std::future::Future;
-use std::pin::Pin;
 use std::time::{SystemTime, UNIX_EPOCH};
 use chrono::{DateTime, Utc, NaiveDate, NaiveDateTime, TimeZone};
-use mime_guess::from_path;
 use serde::{Serialize};
 use tauri::State;
 use tauri::Window;
@@ -20,177 +16,21 @@ use std::collections::HashMap;
 use std::process::Command;
 use std::env;
 use windows::Win32::UI::Shell::IsUserAnAdmin;
-use std::thread::ThreadId;
-use regex::Regex;
-use strsim::damerau_levenshtein;
-use serde_json::Value;
-use std::fs::File;
-use std::io::{Read, Write};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use chrono::Local;
 
-
-
-const CACHE_FILE_PATH: &str = "../backend_properties/cache/search_cache.json";
-const CACHE_SIZE_LIMIT: usize = 50;
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct CacheEntry {
-    pub name: String,
-    pub result: Vec<String>,
-    pub hit: u32,
-    pub search_options: SearchOptions,
-}
-
-// 1. read cache & convert into JSON
-pub fn read_cache() -> Vec<CacheEntry> {
-    let mut file = File::open(CACHE_FILE_PATH).unwrap_or_else(|_| File::create(CACHE_FILE_PATH).unwrap());
-    let mut data = String::new();
-    file.read_to_string(&mut data).unwrap();
-    if data.is_empty() {
-        return vec![];
-    }
-    let cache: Vec<CacheEntry> = serde_json::from_str(&data).unwrap_or_else(|_| vec![]);
-    cache
-}
-
-// 2. Check Cache Entries --> check search Options & Search keywords
-pub fn find_in_cache(keyword: &str, current_options: &SearchOptions) -> Option<Vec<String>> {
-    let cache = read_cache();
-    
-    let entry_opt = cache.iter().find(|entry| {
-        entry.name == keyword && entry.search_options == *current_options
-    });
-
-    if let Some(entry) = entry_opt {
-        return Some(entry.result.clone());
-    }
-
-    None
-}
-
-
-
-
-// 3. write to cache
-pub fn write_cache(cache: &Vec<CacheEntry>) {
-    let mut file = File::create(CACHE_FILE_PATH).unwrap();
-    let data = serde_json::to_string_pretty(&cache).unwrap();
-    file.write_all(data.as_bytes()).unwrap();
-}
-
-// 4. update cache
-pub fn update_cache(keyword: &str, new_results: Vec<String>, current_options: &SearchOptions) {
-    let mut cache = read_cache();
-    
-    let mut found = false;
-    
-    // check duplicate
-    for entry in cache.iter_mut() {
-        if entry.name == keyword && entry.search_options == *current_options {
-            entry.hit += 1;
-            entry.result = new_results.clone();
-            found = true;
-            break;
-        }
-    }
-
-    // add new entries if not duplicated
-    if !found {
-        cache.push(CacheEntry {
-            name: keyword.to_string(),
-            result: new_results,
-            hit: 1,
-            search_options: current_options.clone(),
-        });
-    }
-
-    // LRU
-    if cache.len() > CACHE_SIZE_LIMIT {
-        if let Some(min_hit_entry) = cache.iter().min_by_key(|entry| entry.hit) {
-            let index = cache.iter().position(|entry| entry.name == min_hit_entry.name).unwrap();
-            cache.remove(index);
-        }
-    }
-
-    write_cache(&cache);
-}
-
-
-#[derive(Serialize)]
-struct SearchLog {
-    keyword: String,
-    options: SearchOptions,
-    directory: String,
-    start_time: String,
-    end_time: String,
-    duration: f64,
-    results: Vec<String>,
-    results_count: usize,
-}
-
-fn save_log(log: &SearchLog) -> Result<(), String> {
-    // Log file's name - with current timestamp
-    let timestamp = Local::now().format("%Y-%m-%d_%H-%M-%S").to_string();
-    let filename = format!("../logs/{}_log.json", timestamp);
-
-    let file_path = PathBuf::from(&filename);
-    let mut file = File::create(&file_path).map_err(|e| e.to_string())?;
-    let data = serde_json::to_string_pretty(log).map_err(|e| e.to_string())?;
-    file.write_all(data.as_bytes()).map_err(|e| e.to_string())?;
-    
-    println!("Search log saved at: {}", filename);
-    Ok(())
-}
-
-fn create_search_log(
-    keyword: String,
-    options: SearchOptions,
-    directory: String,
-    start_time: Instant,
-    end_time: Instant,
-    results: Vec<String>,
-) -> SearchLog {
-    SearchLog {
-        keyword,
-        options,
-        directory,
-        start_time: format!("{}", start_time.elapsed().as_secs_f64()),
-        end_time: format!("{}", end_time.elapsed().as_secs_f64()),
-        duration: end_time.duration_since(start_time).as_secs_f64(),
-        results_count: results.len(),
-        results,
-    }
-}
-
-
-
-
-// use by fuzzy-matching
-#[derive(Deserialize, Debug)]
-struct AlgorithmConfig {
-    name: String,
-    threshold: f64,
-}
-
-// use by fuzzy-matching
-fn read_threshold_from_json(algorithm_name: &str) -> Result<f64, String> {
-    let data = fs::read_to_string("../backend_properties/search_properties/fuzzy_properties.json")
-        .map_err(|e| format!("Failed to read file: {}", e))?;
-    println!("File read successfully: {:?}", data);
-
-    let configs: Vec<AlgorithmConfig> = serde_json::from_str(&data)
-        .map_err(|e| format!("Failed to parse JSON: {}", e))?;
-
-    for config in configs {
-        println!("json config name: {:?}", config.name);
-        if config.name == algorithm_name {
-            return Ok(config.threshold);
-        }
-    }
-
-    Err(format!("Algorithm not found: {}", algorithm_name))
-}
+use crate::search_cache::{find_in_cache, update_cache};
+use crate::search_diff;
+use crate::search_engine::{parse_byte_pattern, run_search, MatchRange, WalkState};
+use crate::background_io;
+use crate::media_tags;
+use crate::file_xattr;
+use crate::result_sink::{ChannelSink, ResultSink};
+use crate::result_store::ResultStore;
+use crate::query_terms;
+use crate::search_history;
+use crate::search_stream::{BatchAcks, ResultEmitter, BATCH_MAX_DELAY};
+use crate::search_suggestion::{self, SearchSuggestions};
 
 
 
@@ -198,53 +38,174 @@ fn read_threshold_from_json(algorithm_name: &str) -> Result<f64, String> {
 pub struct FileItem {
     pub file_name: String,
     pub file_path: String,
+    // where the keyword matched inside the match target (character offsets); not kept in the cache
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub matched_ranges: Vec<MatchRange>,
+    // byte pattern search : offsets of the hits in the file (at most BYTE_OFFSET_LIMIT)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub byte_offsets: Vec<u64>,
+    // content index search : BM25 score (results arrive best first)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score: Option<f64>,
 }
 
 #[derive(Serialize, Deserialize, Clone,PartialEq,Debug)]
 pub struct SearchOptions {
     #[serde(rename = "customThreadPoolUse")]
-    custom_thread_pool_use: bool,
+    pub(crate) custom_thread_pool_use: bool,
     #[serde(rename = "threadPoolNum")]
-    thread_pool_num: String,
+    pub(crate) thread_pool_num: String,
     #[serde(rename = "searchScope")]
-    search_scope: String,
+    pub(crate) search_scope: String,
     #[serde(rename = "customFileContUse")]
-    custom_file_cont_use: bool,
+    pub(crate) custom_file_cont_use: bool,
     #[serde(rename = "customPropertyUse")]
-    custom_property_use: bool,
+    pub(crate) custom_property_use: bool,
     #[serde(rename = "customFileSizeUse")]
-    custom_file_size_use: bool,
+    pub(crate) custom_file_size_use: bool,
     #[serde(rename = "sizeMax")]
-    size_max: u64,
+    pub(crate) size_max: u64,
     #[serde(rename = "sizeMin")]
-    size_min: u64,
+    pub(crate) size_min: u64,
     #[serde(rename = "customFileCrtDateUse")]
-    custom_file_crt_date_use: bool,
+    pub(crate) custom_file_crt_date_use: bool,
     #[serde(rename = "crtStart")]
-    crt_start: String,
+    pub(crate) crt_start: String,
     #[serde(rename = "crtEnd")]
-    crt_end: String,
+    pub(crate) crt_end: String,
     #[serde(rename = "customFileModiDateUse")]
-    custom_file_modi_date_use: bool,
+    pub(crate) custom_file_modi_date_use: bool,
     #[serde(rename = "modiStart")]
-    modi_start: String,
+    pub(crate) modi_start: String,
     #[serde(rename = "modiEnd")]
-    modi_end: String,
+    pub(crate) modi_end: String,
     #[serde(rename = "customFileOwnerUse")]
-    custom_file_owner_use: bool,
+    pub(crate) custom_file_owner_use: bool,
     #[serde(rename = "ownerName")]
-    owner_name: String,
+    pub(crate) owner_name: String,
     #[serde(rename = "customFileTypeUse")]
-    custom_file_type_use: bool,
+    pub(crate) custom_file_type_use: bool,
     #[serde(rename = "fileTypeList")]
-    file_type_list: String,
+    pub(crate) file_type_list: String,
+    // hard links (unix) : report a file only under the first of its names the walk finds
+    #[serde(rename = "uniqueInodeUse", default)]
+    pub(crate) unique_inode_use: bool,
     #[serde(rename = "customSymbolicChk")]
-    custom_symbolic_chk: bool,
+    pub(crate) custom_symbolic_chk: bool,
+    // photo / music tags (EXIF, ID3, Vorbis comments, MP4); files without the tag are filtered out
+    #[serde(rename = "customMediaTagUse", default)]
+    pub(crate) custom_media_tag_use: bool,
+    #[serde(rename = "takenStart", default)]
+    pub(crate) taken_start: String,
+    #[serde(rename = "takenEnd", default)]
+    pub(crate) taken_end: String,
+    #[serde(rename = "cameraModel", default)]
+    pub(crate) camera_model: String,
+    // "0" : any, "1" : with GPS position, "2" : without
+    #[serde(rename = "gpsFilter", default)]
+    pub(crate) gps_filter: String,
+    // pixels, 0 : no limit
+    #[serde(rename = "widthMin", default)]
+    pub(crate) width_min: u32,
+    #[serde(rename = "widthMax", default)]
+    pub(crate) width_max: u32,
+    #[serde(rename = "heightMin", default)]
+    pub(crate) height_min: u32,
+    #[serde(rename = "heightMax", default)]
+    pub(crate) height_max: u32,
+    #[serde(rename = "artist", default)]
+    pub(crate) artist: String,
+    #[serde(rename = "album", default)]
+    pub(crate) album: String,
+    #[serde(rename = "title", default)]
+    pub(crate) title: String,
+    // seconds, 0 : no limit
+    #[serde(rename = "durationMin", default)]
+    pub(crate) duration_min: f64,
+    #[serde(rename = "durationMax", default)]
+    pub(crate) duration_max: f64,
+    // extended attribute filter (unix only) : attribute xattrName exists / equals / contains xattrValue
+    #[serde(rename = "customXattrUse", default)]
+    pub(crate) custom_xattr_use: bool,
+    #[serde(rename = "xattrName", default)]
+    pub(crate) xattr_name: String,
+    // "0" : exists, "1" : equals, "2" : contains (case-insensitive)
+    #[serde(rename = "xattrMatch", default)]
+    pub(crate) xattr_match: String,
+    #[serde(rename = "xattrValue", default)]
+    pub(crate) xattr_value: String,
+    // "0" : follow symlinked folders (loops are detected and skipped)
+    // "1" : report the symlink target as a result without descending into it
+    #[serde(rename = "symbolicMode", default)]
+    pub(crate) symbolic_mode: String,
     #[serde(rename = "customSchMethod")]
-    custom_sch_method: String,
+    pub(crate) custom_sch_method: String,
+    // what the keyword is compared against
+    // "0" : file stem, "1" : file name with extension, "2" : path relative to the search root, "3" : absolute path
+    #[serde(rename = "matchTarget", default)]
+    pub(crate) match_target: String,
+    // default method only : every whitespace-separated term must match, in any order
+    // ("quoted phrase" stays literal, -term excludes)
+    #[serde(rename = "multiTermUse", default)]
+    pub(crate) multi_term_use: bool,
+    // where multi-term queries look : "name,path,content" (empty : name, plus content if customFileContUse)
+    #[serde(rename = "termFields", default)]
+    pub(crate) term_fields: String,
+    // "0" : results are sent in batches ("search-result-batch")
+    // "1" : one "search-result" event per result
+    #[serde(rename = "emitMode", default)]
+    pub(crate) emit_mode: String,
+    // "0" : normal, "1" : background (lower CPU / IO priority, rate-limited reads)
+    #[serde(rename = "priority", default)]
+    pub(crate) priority: String,
 
     #[serde(rename = "customLogUse")]
-    custom_log_use: bool,
+    pub(crate) custom_log_use: bool,
+
+    // content search reads every file that decodes as text, not only text/* MIME types (content_replace)
+    #[serde(skip)]
+    pub(crate) content_any_text: bool,
+}
+
+impl SearchOptions {
+    // plain substring search : custom_sch_method "0" (or anything unknown), one term
+    pub(crate) fn is_substring_search(&self) -> bool {
+        !self.multi_term_use && !matches!(self.custom_sch_method.as_str(), "1" | "2" | "3" | "4" | "5")
+    }
+
+    // custom_sch_method "5" : ranked lookup in the content index instead of a walk
+    pub(crate) fn uses_content_index(&self) -> bool {
+        self.custom_sch_method == "5"
+    }
+
+    // file contents are read (content search or byte pattern search)
+    pub(crate) fn reads_content(&self) -> bool {
+        self.custom_file_cont_use || self.custom_sch_method == "4"
+    }
+
+    // results that only depend on names and folders, so folder mtimes tell when they are stale
+    // (file edits, property / media tag / xattr changes leave the folder mtime alone)
+    pub(crate) fn is_cacheable(&self) -> bool {
+        !self.uses_content_index()
+            && !self.reads_content()
+            && !self.custom_property_use
+            && !(self.multi_term_use && query_terms::TermFields::from_options(self).content)
+    }
+
+    pub(crate) fn is_background(&self) -> bool {
+        self.priority == background_io::PRIORITY_BACKGROUND
+    }
+
+    // cache key : the options without the ones that do not change the results
+    pub(crate) fn cache_key(&self) -> String {
+        let mut options = self.clone();
+        options.custom_thread_pool_use = false;
+        options.thread_pool_num = String::new();
+        options.custom_log_use = false;
+        options.emit_mode = String::new();
+        options.priority = String::new();
+        serde_json::to_string(&options).unwrap_or_default()
+    }
 }
 
 #[derive(Clone)]
@@ -252,6 +213,7 @@ pub struct SearchProcess {
     is_cancelled: Arc<Mutex<bool>>,
     is_completed: Arc<Mutex<bool>>,
     id: String,
+    batch_acks: Arc<BatchAcks>,
 }
 
 impl SearchProcess {
@@ -260,9 +222,14 @@ impl SearchProcess {
             is_cancelled: Arc::new(Mutex::new(false)),
             is_completed: Arc::new(Mutex::new(false)),
             id: uuid::Uuid::new_v4().to_string(),
+            batch_acks: Arc::new(BatchAcks::new()),
         }
     }
 
+    pub fn batch_acks(&self) -> &BatchAcks {
+        &self.batch_acks
+    }
+
     pub async fn cancel(&self) {
         if self.is_completed().await {
             println!("Search is already completed, cannot cancel.");
@@ -299,7 +266,7 @@ impl SearchProcess {
 
 #[derive(Serialize,Debug,Clone)]
 pub struct SearchProcessInfo {
-    id: String,
+    pub(crate) id: String,
     is_cancelled: bool,
 }
 
@@ -314,17 +281,92 @@ impl SearchProcessInfo {
 
 
 
+const FINISHED_SEARCH_LIMIT: usize = 10;
+const SEARCH_STATE_PROPERTIES_PATH: &str = "../backend_properties/search_properties/search_state_properties.json";
+const DEFAULT_FINISHED_SEARCH_TTL_SECS: u64 = 30 * 60;
+
+#[derive(Deserialize, Debug)]
+struct SearchStateProperties {
+    // how long finished results stay available for export / refine
+    #[serde(rename = "finishedSearchTtlSecs")]
+    finished_search_ttl_secs: u64,
+}
+
+fn read_finished_search_ttl() -> Duration {
+    let secs = fs::read_to_string(SEARCH_STATE_PROPERTIES_PATH)
+        .ok()
+        .and_then(|data| serde_json::from_str::<SearchStateProperties>(&data).ok())
+        .map(|properties| properties.finished_search_ttl_secs)
+        .unwrap_or(DEFAULT_FINISHED_SEARCH_TTL_SECS);
+    Duration::from_secs(secs)
+}
+
+// results of a finished search, kept for export and refine_search
+#[derive(Clone)]
+pub struct FinishedSearch {
+    pub keyword: String,
+    pub directory: String,
+    pub options: SearchOptions,
+    pub results: Arc<ResultStore>,
+    finished_at: Instant,
+}
+
+impl FinishedSearch {
+    pub fn new(keyword: String, directory: String, options: SearchOptions, results: Arc<ResultStore>) -> Self {
+        FinishedSearch {
+            keyword,
+            directory,
+            options,
+            results,
+            finished_at: Instant::now(),
+        }
+    }
+}
+
 pub struct AppState {
     search_processes: Mutex<HashMap<String, Arc<SearchProcess>>>,
+    finished_searches: Mutex<HashMap<String, FinishedSearch>>,
+    finished_search_ttl: Duration,
 }
 
 impl AppState {
     pub fn new() -> Self {
         AppState {
             search_processes: Mutex::new(HashMap::new()),
+            finished_searches: Mutex::new(HashMap::new()),
+            finished_search_ttl: read_finished_search_ttl(),
         }
     }
 
+    pub async fn add_finished_search(&self, process_id: String, search: FinishedSearch) {
+        let mut finished = self.finished_searches.lock().await;
+        finished.insert(process_id, search);
+
+        let ttl = self.finished_search_ttl;
+        finished.retain(|_, search| search.finished_at.elapsed() < ttl);
+
+        // drop the oldest ones
+        while finished.len() > FINISHED_SEARCH_LIMIT {
+            let oldest = finished.iter()
+                .min_by_key(|(_, search)| search.finished_at)
+                .map(|(id, _)| id.clone());
+            match oldest {
+                Some(id) => { finished.remove(&id); }
+                None => break,
+            }
+        }
+    }
+
+    pub async fn get_finished_search(&self, process_id: &str) -> Option<FinishedSearch> {
+        let mut finished = self.finished_searches.lock().await;
+        if finished.get(process_id)?.finished_at.elapsed() >= self.finished_search_ttl {
+            println!("Finished search expired: {}", process_id);
+            finished.remove(process_id);
+            return None;
+        }
+        finished.get(process_id).cloned()
+    }
+
     pub async fn add_process(&self, process_id: String, process: Arc<SearchProcess>) {
         let mut processes = self.search_processes.lock().await;
         processes.insert(process_id, process);
@@ -341,15 +383,6 @@ impl AppState {
     }
 }
 
-// For Check ThreadPool
-async fn track_and_print_thread_ids(thread_ids: Arc<Mutex<HashSet<ThreadId>>>) {
-    let ids_lock = thread_ids.lock().await;
-    println!("Unique thread IDs used: {:?}", ids_lock);
-    println!("Total number of unique threads: {}", ids_lock.len());
-}
-
-
-
 #[tauri::command]
 pub async fn cancel_search(
     process_id: String,
@@ -365,26 +398,6 @@ pub async fn cancel_search(
     Ok(())
 }
 
-// for cache check if cache value is located at or under input directory
-fn is_path_in_directory(file_path: &Path, dir_path: &Path) -> bool {
-    let canonical_file_path = file_path.canonicalize().unwrap_or_else(|_| file_path.to_path_buf());
-    let canonical_dir_path = dir_path.canonicalize().unwrap_or_else(|_| dir_path.to_path_buf());
-
-    println!("Comparing file path: {:?}", canonical_file_path);
-    println!("With directory path: {:?}", canonical_dir_path);
-
-    if canonical_file_path.starts_with(&canonical_dir_path) {
-        println!("File is within directory.");
-        true
-    } else {
-        println!("File is NOT within directory.");
-        false
-    }
-}
-
-
-
-
 #[tauri::command]
 pub async fn search_files<'a>(
     window: Window,
@@ -400,23 +413,25 @@ pub async fn search_files<'a>(
     if !dir_path.exists() {
         return Err(format!("Directory does not exist: {:?}", dir_path));
     }
+    // once here, not once per file
+    let byte_pattern = parse_byte_pattern(&keyword, &options)?;
 
     let start_time = Instant::now();
 
+    // background searches slow down while this one runs
+    let _foreground_job = (!options.is_background()).then(background_io::foreground_job);
 
-    let cached_paths: Arc<Mutex<HashSet<String>>> = Arc::new(Mutex::new(HashSet::new()));
-
-
-    if let Some(cached_results) = find_in_cache(&keyword, &options) {
 
-        let mut cached_paths_lock = cached_paths.lock().await;
+    // one lookup per search; the walk below only emits what the cache did not
+    let mut cached_paths: HashSet<String> = HashSet::new();
+    let mut cached_items: Vec<FileItem> = Vec::new();
+    let cached_results = find_in_cache(&dir_path, &keyword, &options);
 
+    if let Some(cached_results) = cached_results.clone() {
         for file_path in cached_results {
             let path = Path::new(&file_path);
 
-            let is_in_directory = is_path_in_directory(path, &dir_path);
-
-            if !path.exists() || !is_in_directory{
+            if !path.exists() {
                 continue;
             }
 
@@ -429,14 +444,16 @@ pub async fn search_files<'a>(
                 _ => {},
             }
 
-            cached_paths_lock.insert(file_path.clone());
-
             let file_item = FileItem {
-                file_name: path.file_name().unwrap().to_string_lossy().to_string(),
+                file_name: path.file_name().unwrap_or_default().to_string_lossy().to_string(),
                 file_path: file_path.clone(),
+                matched_ranges: vec![],
+                byte_offsets: vec![],
+                score: None,
             };
 
-            window.emit("search-result", file_item).expect("Failed to emit search result from cache");
+            cached_paths.insert(file_path);
+            cached_items.push(file_item);
         }
     }
 
@@ -458,30 +475,40 @@ pub async fn search_files<'a>(
     window.emit("process-info", process_info.clone()).expect("Failed to emit process info");
     println!("Backend process ID: {:?}", process.id);
 
-    let result = Arc::new(Mutex::new(Vec::new()));
-    let sent_files = Arc::new(Mutex::new(HashSet::new()));
+    let mut emitter = ResultEmitter::new(window.clone(), process_id.clone(), &options, process.batch_acks());
+    for file_item in cached_items {
+        emitter.push(file_item).await;
+    }
+
+    let mut store = ResultStore::new(&process_id);
     let (tx, mut rx) = mpsc::channel(100);
-    let tx = Arc::new(Mutex::new(tx));
+    // the channel closes when the walk drops the sink
+    let sink: Arc<dyn ResultSink> = Arc::new(ChannelSink::new(tx));
 
-    // For Check ThreadPool
-    let thread_ids = Arc::new(Mutex::new(HashSet::<ThreadId>::new()));
+    let walk_state = Arc::new(WalkState::new(&dir_path).with_byte_pattern(byte_pattern));
+    let search_root = dir_path.clone();
 
     let process_clone = Arc::clone(&process);
-    let result_clone = Arc::clone(&result);
-    let tx_clone = Arc::clone(&tx);
-    
-    // For Check ThreadPool
-    let thread_ids_clone = Arc::clone(&thread_ids);
+    let walk_state_clone = Arc::clone(&walk_state);
 
     let keyword_for_spawn = keyword.clone();
     let options_for_spawn = options.clone();
+    let window_for_spawn = window.clone();
 
-    tokio::spawn(async move {
+    background_io::spawn(options.is_background(), async move {
         let process_clone_for_cancel = Arc::clone(&process_clone);
         
         tokio::select! {
-            _ = search_in_directory(dir_path, keyword_for_spawn.clone(), result_clone, process_clone, options_for_spawn, tx_clone, thread_ids_clone) => {
-                println!("Search completed");
+            outcome = run_search(dir_path, keyword_for_spawn.clone(), process_clone, options_for_spawn, sink, walk_state_clone) => {
+                match outcome {
+                    Ok(()) => println!("Search completed"),
+                    Err(e) => {
+                        println!("Search failed: {}", e);
+                        if let Err(e) = window_for_spawn.emit("search-error", e) {
+                            println!("Failed to emit search error: {:?}", e);
+                        }
+                    }
+                }
             }
             _ = async {
                 loop {
@@ -495,39 +522,41 @@ pub async fn search_files<'a>(
                 println!("Cancel loop executed!");
             }
         }
-        drop(tx);
     });
     
 
-    while let Some(file_item) = rx.recv().await {
-        let mut sent_files_lock = sent_files.lock().await;
-        
-        if sent_files_lock.contains(&file_item.file_path) {
-            continue;
-        }
-    
-        if let Some(cached_results) = find_in_cache(&keyword, &options) {
-            if cached_results.contains(&file_item.file_path) {
-                println!("File already in cache, skipping frontend emit: {}", file_item.file_path);
-            } else {
-                if let Err(e) = window.emit("search-result", file_item.clone()) {
-                    println!("Failed to emit search result: {:?}", e);
-                }
+    let mut flush_timer = tokio::time::interval(BATCH_MAX_DELAY);
+    loop {
+        let file_item = tokio::select! {
+            received = rx.recv() => match received {
+                Some(file_item) => file_item,
+                None => break,
+            },
+            _ = flush_timer.tick() => {
+                emitter.flush_if_due().await;
+                continue;
             }
-        }else {
-            if let Err(e) = window.emit("search-result", file_item.clone()) {
-                println!("Failed to emit search result: {:?}", e);
+        };
+
+        if cached_paths.contains(&file_item.file_path) {
+            if store.push(file_item) {
+                println!("File already in cache, skipping frontend emit");
             }
+        } else if store.push(file_item.clone()) {
+            emitter.push(file_item).await;
         }
-    
-        sent_files_lock.insert(file_item.file_path.clone());
-    
-        let mut result_lock = result.lock().await;
-        result_lock.push(file_item);
     }
-    
+    emitter.flush().await;
+    store.finish();
+
 
-    track_and_print_thread_ids(Arc::clone(&thread_ids)).await;
+    walk_state.print_thread_ids().await;
+
+    for symlink_loop in walk_state.take_symlink_loops().await {
+        if let Err(e) = window.emit("symlink-loop", symlink_loop) {
+            println!("Failed to emit symlink loop: {:?}", e);
+        }
+    }
 
     process.mark_as_completed().await;
     
@@ -536,44 +565,147 @@ pub async fn search_files<'a>(
 
     state.remove_process(&process_id).await;
 
-    let final_results = {
-        let result_lock = result.lock().await;
-        result_lock.iter().map(|file_item| file_item.file_path.clone()).collect::<Vec<String>>()
-    };
-    update_cache(&keyword, final_results, &options);
+    let results_count = store.len();
+    let is_zero_result = store.is_empty() && cached_paths.is_empty();
+    let store = Arc::new(store);
+    // a cancelled walk only has part of the results
+    if !process.is_cancelled().await {
+        let dir_mtimes = walk_state.take_dir_mtimes().await;
+        let window = window.clone();
+        let process_id = process_id.clone();
+        let keyword = keyword.clone();
+        let directory = directory_clone.clone();
+        let options = options.clone();
+        let store = Arc::clone(&store);
+        // stats every result and writes files (spilled results are read back from disk) : off the async workers
+        let finished = background_io::spawn_blocking(options.is_background(), move || {
+            emit_search_diff(&window, &process_id, &keyword, &directory, &options, &store, cached_results.as_deref());
+            update_cache(&search_root, &keyword, store.paths(), &options, dir_mtimes);
+        });
+        if let Err(e) = finished.await {
+            println!("Failed to store search results: {:?}", e);
+        }
+    }
+
+    state.add_finished_search(
+        process_id.clone(),
+        FinishedSearch::new(keyword.clone(), directory_clone.clone(), options.clone(), store),
+    ).await;
+
+    if is_zero_result && options.is_substring_search() && !process.is_cancelled().await {
+        let seen_names = walk_state.take_seen_names().await;
+        let suggestions = search_suggestion::suggest_keywords(&keyword, &seen_names);
+        if !suggestions.is_empty() {
+            let payload = SearchSuggestions::new(process_id.clone(), keyword.clone(), directory_clone.clone(), suggestions);
+            if let Err(e) = window.emit("search-suggestions", payload) {
+                println!("Failed to emit search suggestions: {:?}", e);
+            }
+        }
+    }
 
      let elapsed_time = start_time.elapsed();
 
      window.emit("search-time", elapsed_time.as_secs_f64()).expect("Failed to emit search time");
 
     if options.custom_log_use {
-        let final_results = {
-            let result_lock = result.lock().await;
-            result_lock.iter().map(|file_item| file_item.file_path.clone()).collect::<Vec<String>>()
-        };
+        let keyword = keyword.clone();
+        let history_options = options.clone();
+        let duration = elapsed_time.as_secs_f64();
+        // file append (and now and then a compaction) under the history lock : off the async workers
+        let recorded = background_io::spawn_blocking(options.is_background(), move || {
+            search_history::record_search(keyword, directory_clone, history_options, duration, results_count)
+        });
+        match recorded.await {
+            Ok(Ok(_)) => {}
+            Ok(Err(e)) => println!("Failed to save search history: {}", e),
+            Err(e) => println!("Failed to save search history: {:?}", e),
+        }
+    }
+
+
+
+    Ok(process_info)
+}
+
+
 
-        let end_time = Instant::now();
 
-        let log = create_search_log(
-            keyword.clone(),
-            options.clone(),
-            directory_clone,
-            start_time,
-            end_time,
-            final_results,
-        );
 
-        if let Err(e) = save_log(&log) {
-            println!("Failed to save log: {}", e);
+// compare with the previous run of the same query (or the cached paths)
+// the run itself is only stored when the user opted into logs (customLogUse)
+fn emit_search_diff(
+    window: &Window,
+    process_id: &str,
+    keyword: &str,
+    directory: &str,
+    options: &SearchOptions,
+    store: &ResultStore,
+    cached_results: Option<&[String]>,
+) {
+    let previous_run = search_diff::latest_run(keyword, directory, options);
+    let run = if options.custom_log_use {
+        match search_diff::save_run(keyword, directory, options, store.paths()) {
+            Ok(run) => run,
+            Err(e) => {
+                println!("Failed to save search run: {}", e);
+                return;
+            }
         }
+    } else {
+        search_diff::new_run(keyword, directory, options, store.paths())
+    };
+
+    let diff = match (previous_run, cached_results) {
+        (Some(base), _) => search_diff::diff_against_run(Some(process_id), &base, &run),
+        (None, Some(cached)) => search_diff::diff_against_paths(process_id, cached, &run),
+        (None, None) => return,
+    };
+    if let Err(e) = window.emit("search-diff", diff) {
+        println!("Failed to emit search diff: {:?}", e);
     }
+}
 
 
 
-    Ok(process_info)
-}
+// run a search without a window and collect every match
+pub async fn collect_search_results(
+    dir_path: PathBuf,
+    keyword: String,
+    options: SearchOptions,
+) -> Result<Vec<FileItem>, String> {
+    if !dir_path.exists() {
+        return Err(format!("Directory does not exist: {:?}", dir_path));
+    }
+
+    let byte_pattern = parse_byte_pattern(&keyword, &options)?;
+    let process = Arc::new(SearchProcess::new());
+    let walk_state = Arc::new(WalkState::new(&dir_path).with_byte_pattern(byte_pattern));
+    let (tx, mut rx) = mpsc::channel(100);
+    let sink: Arc<dyn ResultSink> = Arc::new(ChannelSink::new(tx));
+
+    let walk = background_io::spawn(options.is_background(), run_search(
+        dir_path.clone(),
+        keyword.clone(),
+        process,
+        options.clone(),
+        sink,
+        Arc::clone(&walk_state),
+    ));
+
+    let mut sent_files = HashSet::new();
+    let mut items = Vec::new();
+    while let Some(file_item) = rx.recv().await {
+        if sent_files.insert(file_item.file_path.clone()) {
+            items.push(file_item);
+        }
+    }
 
+    walk.await.map_err(|e| format!("Task failed: {:?}", e))??;
 
+    let paths = items.iter().map(|file_item| file_item.file_path.clone());
+    update_cache(&dir_path, &keyword, paths, &options, walk_state.take_dir_mtimes().await);
+    Ok(items)
+}
 
 
 
@@ -658,6 +790,38 @@ fn get_file_owner(path: &std::path::Path) -> Option<String> {
     None
 }
 
+#[cfg(windows)]
+pub(crate) fn lookup_file_owner(path: &Path, _metadata: &fs::Metadata) -> Option<String> {
+    get_file_owner(path)
+}
+
+// user name of the owner; the numeric uid when it has no passwd entry (e.g. a disk from another machine)
+#[cfg(unix)]
+pub(crate) fn lookup_file_owner(_path: &Path, metadata: &fs::Metadata) -> Option<String> {
+    use std::ffi::CStr;
+    use std::os::unix::fs::MetadataExt;
+
+    let owner_uid = metadata.uid();
+    let mut passwd: libc::passwd = unsafe { std::mem::zeroed() };
+    let mut found: *mut libc::passwd = std::ptr::null_mut();
+    let mut buffer: Vec<libc::c_char> = vec![0; 1024];
+    loop {
+        let code = unsafe { libc::getpwuid_r(owner_uid, &mut passwd, buffer.as_mut_ptr(), buffer.len(), &mut found) };
+        // the entry did not fit : retry with a bigger buffer
+        if code == libc::ERANGE && buffer.len() < 1024 * 1024 {
+            buffer.resize(buffer.len() * 2, 0);
+            continue;
+        }
+        break;
+    }
+
+    if found.is_null() || passwd.pw_name.is_null() {
+        return Some(owner_uid.to_string());
+    }
+    let name = unsafe { CStr::from_ptr(passwd.pw_name) };
+    Some(name.to_string_lossy().to_string())
+}
+
 fn truncate_to_date(date: DateTime<Utc>) -> DateTime<Utc> {
     let naive_date = date.naive_utc().date();
     Utc.from_utc_datetime(&naive_date.and_hms(0, 0, 0))
@@ -671,7 +835,7 @@ fn parse_date_to_rfc3339(date_str: &str) -> Result<DateTime<Utc>, String> {
         .map_err(|e| format!("Failed to parse date: {}", e))
 }
 
-fn should_filter_file_by_metadata(path: &Path, options: &SearchOptions) -> bool {
+pub(crate) fn should_filter_file_by_metadata(path: &Path, options: &SearchOptions) -> bool {
     println!("Filtering file by metadata: {:?}", path);
     if let Ok(metadata) = fs::metadata(path) {
         if options.custom_file_size_use && metadata.is_file() {
@@ -736,31 +900,25 @@ fn should_filter_file_by_metadata(path: &Path, options: &SearchOptions) -> bool
             }
 
             let owner_name = options.owner_name.to_lowercase();
-            #[cfg(windows)]
-            {
-                if let Some(actual_owner_name) = get_file_owner(path) {
-                    if !actual_owner_name.to_lowercase().contains(&owner_name) {
-                        println!("File or folder filtered by owner: {}", actual_owner_name);
-                        return true;
-                    }
-                } else {
-                    println!("Failed to retrieve owner information. Skipping file or folder.");
-                    return true;
-                }
-            }
-
-            #[cfg(unix)]
-            {
-                use nix::unistd::Uid;
-                let owner_uid = metadata.uid();
-                let actual_owner_name = Uid::from_raw(owner_uid).to_string();
+            if let Some(actual_owner_name) = lookup_file_owner(path, &metadata) {
                 if !actual_owner_name.to_lowercase().contains(&owner_name) {
                     println!("File or folder filtered by owner: {}", actual_owner_name);
                     return true;
                 }
+            } else {
+                println!("Failed to retrieve owner information. Skipping file or folder.");
+                return true;
             }
         }
 
+        if options.custom_media_tag_use && metadata.is_file() && is_filtered_by_media_tags(path, options) {
+            return true;
+        }
+
+        if options.custom_xattr_use && metadata.is_file() && file_xattr::is_filtered_by_xattr(path, options) {
+            return true;
+        }
+
         if options.custom_file_type_use && metadata.is_file() {
             if let Some(ext) = path.extension().and_then(|s| s.to_str()) {
                 let allowed_extensions: Vec<&str> = options.file_type_list.split_whitespace().collect();
@@ -776,305 +934,74 @@ fn should_filter_file_by_metadata(path: &Path, options: &SearchOptions) -> bool
 }
 
 
-fn is_text_file(path: &Path) -> bool {
-    println!("Checking if file is text: {:?}", path);
-    if let Some(mime) = from_path(path).first() {
-        return mime.type_() == "text";
-    }
-    false
-}
-
-fn search_in_directory<'a>(
-    dir: PathBuf,
-    keyword: String,
-    result: Arc<Mutex<Vec<FileItem>>>,
-    process: Arc<SearchProcess>,
-    options: SearchOptions,
-    tx: Arc<Mutex<Sender<FileItem>>>,
-    thread_ids: Arc<Mutex<HashSet<ThreadId>>>, // For Check ThreadPool
-) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
-    Box::pin(async move {
-        let mut handles = Vec::new();
-
-        match async_fs::read_dir(&dir).await {
-            Ok(mut entries) => {
-                while let Ok(Some(entry)) = entries.next_entry().await {
-
-                    tokio::task::yield_now().await;
-
-                    let thread_id = std::thread::current().id();
-                    let mut thread_ids_lock = thread_ids.lock().await;
-                    thread_ids_lock.insert(thread_id);
-
-                    let path = entry.path();
-                    let keyword = keyword.clone();
-
-                    if process.is_cancelled().await {
-                        println!("Search cancelled during directory scan.");
-                        return Ok(());
-                    }
-
-                    let metadata = match fs::metadata(&path) {
-                        Ok(meta) => meta,
-                        Err(e) => {
-                            if e.kind() == std::io::ErrorKind::PermissionDenied {
-                                println!("Skipping file due to access error: {}", e);
-                                continue;
-                            } else {
-                                return Err(format!("Error reading metadata: {}", e));
-                            }
-                        }
-                    };
-
-                    if path.is_symlink() && !options.custom_symbolic_chk {
-                        continue;
-                    }
-
-                    // Metadata ( file Property chk)
-                    if options.custom_property_use {
-                        if should_filter_file_by_metadata(&path, &options) {
-                            println!("File filtered by metadata: {:?}", path);
-                            continue;
-                        }
-                    }
-
-                    match options.custom_sch_method.as_str() {
-                        "1" => {
-                            search_with_regex(&path, &keyword, &options, &metadata, &tx).await?;
-                        }
-                        "2" => {
-                            search_with_fuzzy_damerau_levenshtein(&path, &keyword, &options, &metadata, &tx).await?;
-                        }
-                        "3" => {
-                            search_with_fuzzy_jaccard_similarity(&path, &keyword, &options, &metadata, &tx).await?;
-                        }
-                        _ => {
-                            search_default(&path, &keyword, &options, &metadata, &tx).await?;
-                        }
-                    }
-
-                    if metadata.is_dir() {
-                        let handle = tokio::spawn({
-                            let process = Arc::clone(&process);
-                            let tx = Arc::clone(&tx);
-                            let result_clone = Arc::clone(&result);
-                            let options_clone = options.clone();
-
-                            // For Check ThreadPool
-                            let thread_ids_clone = Arc::clone(&thread_ids);
-
-                            async move {
-                                let sub_result = Vec::new();
-                                if let Err(e) = search_in_directory(path, keyword, result_clone, process, options_clone, tx, thread_ids_clone).await {
-                                    if e.contains("Access is denied") || e.contains("Permission denied") {
-                                        println!("Skipping directory due to access error: {}", e);
-                                    } else {
-                                        return Err(e);
-                                    }
-                                }
-                                Ok::<_, String>(sub_result)
-                            }
-                        });
-                        handles.push(handle);
-                    }
-                }
-
-                for handle in handles {
-                    match handle.await {
-                        Ok(Ok(mut sub_result)) => {
-                            let mut result_lock = result.lock().await;
-                            result_lock.append(&mut sub_result);
-                        }
-                        Ok(Err(e)) => return Err(e),
-                        Err(e) => return Err(format!("Task failed: {:?}", e)),
-                    }
-                }
-
-                Ok(())
-            }
-            Err(e) => {
-                if e.kind() == std::io::ErrorKind::PermissionDenied {
-                    println!("Skipping directory due to access error: {}", e);
-                    Ok(())
-                } else {
-                    Err(format!("Failed to read directory: {}", e.to_string()))
-                }
-            }
+// text filters match case-insensitively; a file missing a filtered tag is filtered out
+fn is_filtered_by_media_tags(path: &Path, options: &SearchOptions) -> bool {
+    let tags = match media_tags::read_media_tags(path) {
+        Some(tags) => tags,
+        None => {
+            println!("File filtered by media tags (no tags): {:?}", path);
+            return true;
         }
-    })
-}
-
-
-async fn search_default(
-    path: &Path,
-    keyword: &str,
-    options: &SearchOptions,
-    metadata: &fs::Metadata,
-    tx: &Arc<Mutex<Sender<FileItem>>>,
-) -> Result<(), String> {
-    let file_name = path.file_stem().and_then(|name| name.to_str()).unwrap_or_default();
-
-    if options.search_scope == "1" && metadata.is_dir() {
-        return Ok(());
-    } else if options.search_scope == "2" && !metadata.is_dir() {
-        return Ok(());
-    }
-
-    let is_file_name_match = file_name.contains(keyword);
+    };
 
-    let mut is_file_content_match = false;
-    if metadata.is_file() && options.custom_file_cont_use && is_text_file(path) {
-        let content = async_fs::read_to_string(&path).await.unwrap_or_else(|_| String::new());
-        if content.contains(keyword) {
-            is_file_content_match = true;
+    let taken_date = tags.taken_at().map(|taken| truncate_to_date(Utc.from_utc_datetime(&taken)));
+    if let Ok(start_time) = parse_date_to_rfc3339(&options.taken_start) {
+        if taken_date.map_or(true, |taken| taken < truncate_to_date(start_time)) {
+            println!("File filtered by date taken: {:?}", tags.date_taken);
+            return true;
         }
     }
-
-    if is_file_name_match || is_file_content_match {
-        let file_item = FileItem {
-            file_name: file_name.to_string(),
-            file_path: path.to_string_lossy().to_string(),
-        };
-
-        let tx_lock = tx.lock().await;
-        tx_lock.send(file_item).await.unwrap();
-        println!("File or directory matched");
+    if let Ok(end_time) = parse_date_to_rfc3339(&options.taken_end) {
+        if taken_date.map_or(true, |taken| taken > truncate_to_date(end_time)) {
+            println!("File filtered by date taken: {:?}", tags.date_taken);
+            return true;
+        }
     }
 
-    Ok(())
-}
-
-
-
-async fn search_with_regex(
-    path: &Path,
-    keyword: &str,
-    options: &SearchOptions,
-    metadata: &fs::Metadata,
-    tx: &Arc<Mutex<Sender<FileItem>>>,
-) -> Result<(), String> {
-    let file_name = path.file_stem().and_then(|name| name.to_str()).unwrap_or_default();
-
-    if options.search_scope == "1" && metadata.is_dir() {
-        return Ok(());
-    } else if options.search_scope == "2" && !metadata.is_dir() {
-        return Ok(());
+    let text_filters = [
+        (&options.camera_model, &tags.camera_model),
+        (&options.artist, &tags.artist),
+        (&options.album, &tags.album),
+        (&options.title, &tags.title),
+    ];
+    for (wanted, actual) in text_filters {
+        if wanted.is_empty() {
+            continue;
+        }
+        let found = actual
+            .as_ref()
+            .map_or(false, |actual| actual.to_lowercase().contains(&wanted.to_lowercase()));
+        if !found {
+            println!("File filtered by media tag: {:?} (wanted {})", actual, wanted);
+            return true;
+        }
     }
 
-
-    let regex = match Regex::new(keyword) {
-        Ok(r) => r,
-        Err(e) => return Err(format!("Invalid regex pattern: {}", e)),
+    let gps_wanted = match options.gps_filter.as_str() {
+        "1" => Some(true),
+        "2" => Some(false),
+        _ => None,
     };
-
-    let is_file_name_match = regex.is_match(file_name);
-    let mut is_file_content_match = false;
-
-    if metadata.is_file() && options.custom_file_cont_use && is_text_file(path) {
-        let content = async_fs::read_to_string(&path).await.unwrap_or_else(|_| String::new());
-        is_file_content_match = regex.is_match(&content);
-    }
-
-    if is_file_name_match || is_file_content_match {
-        let file_item = FileItem {
-            file_name: file_name.to_string(),
-            file_path: path.to_string_lossy().to_string(),
-        };
-
-        let tx_lock = tx.lock().await;
-        tx_lock.send(file_item).await.unwrap();
-        println!("File or directory matched with regex");
-    }
-
-    Ok(())
-}
-
-
-async fn search_with_fuzzy_damerau_levenshtein(
-    path: &Path,
-    keyword: &str,
-    options: &SearchOptions,
-    metadata: &fs::Metadata,
-    tx: &Arc<Mutex<Sender<FileItem>>>,
-) -> Result<(), String> {
-    println!("Performing fuzzy-based search on: {:?}", path);
-    let threshold = read_threshold_from_json("Damerau-Levenshtein").unwrap_or(2.0);
-    let file_name = path.file_stem().and_then(|name| name.to_str()).unwrap_or_default();
-    println!("Performing fuzzy-based Damerau-levenshtein threshold: {:?}", threshold);
-
-    if options.search_scope == "1" && metadata.is_dir() {
-        return Ok(());
-    } else if options.search_scope == "2" && !metadata.is_dir() {
-        return Ok(());
-    }
-    
-
-    let distance = damerau_levenshtein(file_name, keyword);
-    println!("distance: {:?}", distance);
-    if (distance as f64) <= threshold {
-        let file_item = FileItem {
-            file_name: file_name.to_string(),
-            file_path: path.to_string_lossy().to_string(),
-        };
-
-        let tx_lock = tx.lock().await;
-        tx_lock.send(file_item).await.unwrap();
-        println!("File or directory matched by fuzzy search");
-    }
-    
-    Ok(())
-}
-
-
-
-fn jaccard_similarity(a: &str, b: &str) -> f64 {
-    let a_grams: HashSet<_> = a.chars().collect();
-    let b_grams: HashSet<_> = b.chars().collect();
-
-    let intersection_size = a_grams.intersection(&b_grams).count();
-    let union_size = a_grams.union(&b_grams).count();
-
-    if union_size == 0 {
-        return 0.0;
+    if gps_wanted.is_some() && tags.has_gps != gps_wanted {
+        println!("File filtered by GPS position: {:?}", tags.has_gps);
+        return true;
     }
-    intersection_size as f64 / union_size as f64
-}
 
-async fn search_with_fuzzy_jaccard_similarity(
-    path: &Path,
-    keyword: &str,
-    options: &SearchOptions,
-    metadata: &fs::Metadata,
-    tx: &Arc<Mutex<Sender<FileItem>>>,
-) -> Result<(), String> {
-    let file_name = path.file_stem().and_then(|name| name.to_str()).unwrap_or_default();
-    
-    if options.search_scope == "1" && metadata.is_dir() {
-        return Ok(());
-    } else if options.search_scope == "2" && !metadata.is_dir() {
-        return Ok(());
+    let out_of_range = |value: Option<f64>, min: f64, max: f64| {
+        (min > 0.0 || max > 0.0)
+            && value.map_or(true, |value| value < min || (max > 0.0 && value > max))
+    };
+    if out_of_range(tags.width.map(f64::from), options.width_min as f64, options.width_max as f64)
+        || out_of_range(tags.height.map(f64::from), options.height_min as f64, options.height_max as f64)
+    {
+        println!("File filtered by image size: {:?} x {:?}", tags.width, tags.height);
+        return true;
     }
-
-    let jaccard_threshold = read_threshold_from_json("Jaccard-Similarity")
-    .map(|threshold| threshold as f64)
-    .unwrap_or(0.5);
-
-    let similarity = jaccard_similarity(file_name, keyword);
-    println!("Jaccard similarity between '{}' and '{}': {}", file_name, keyword, similarity);
-
-    if similarity >= jaccard_threshold {
-        let file_item = FileItem {
-            file_name: file_name.to_string(),
-            file_path: path.to_string_lossy().to_string(),
-        };
-
-        let tx_lock = tx.lock().await;
-        tx_lock.send(file_item).await.unwrap();
-        println!("File or directory matched by Jaccard similarity");
+    if out_of_range(tags.duration, options.duration_min, options.duration_max) {
+        println!("File filtered by duration: {:?}", tags.duration);
+        return true;
     }
 
-    Ok(())
+    false
 }
 
-
-
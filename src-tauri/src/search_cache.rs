@@ -0,0 +1,310 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, UNIX_EPOCH};
+
+use crate::search::SearchOptions;
+
+const CACHE_FILE_PATH: &str = "../backend_properties/cache/search_cache.json";
+const CACHE_PROPERTIES_PATH: &str = "../backend_properties/cache/cache_properties.json";
+
+// dirty entries are written back to disk at most this often
+const CACHE_FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+
+// folders whose mtime is tracked per entry; bigger trees are not cached
+pub(crate) const DIR_MTIME_LIMIT: usize = 20_000;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum EvictionPolicy {
+    // least recently used
+    Lru,
+    // least frequently used, ties broken by recency
+    Lfu,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct CacheProperties {
+    #[serde(default = "default_policy")]
+    policy: EvictionPolicy,
+    #[serde(rename = "maxEntries", default = "default_max_entries")]
+    max_entries: usize,
+    #[serde(rename = "maxBytes", default = "default_max_bytes")]
+    max_bytes: usize,
+}
+
+fn default_policy() -> EvictionPolicy {
+    EvictionPolicy::Lru
+}
+
+fn default_max_entries() -> usize {
+    50
+}
+
+fn default_max_bytes() -> usize {
+    32 * 1024 * 1024
+}
+
+impl Default for CacheProperties {
+    fn default() -> Self {
+        CacheProperties {
+            policy: default_policy(),
+            max_entries: default_max_entries(),
+            max_bytes: default_max_bytes(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CacheEntry {
+    pub root: String,
+    pub keyword: String,
+    pub search_options: SearchOptions,
+    pub result: Vec<String>,
+    pub hit: u32,
+    // value of the access counter at the last lookup / update
+    last_access: u64,
+    // folder -> mtime (nanoseconds since epoch) when the results were collected
+    dir_mtimes: HashMap<String, u64>,
+}
+
+impl CacheEntry {
+    // rough heap size, used for the byte limit
+    fn estimated_bytes(&self) -> usize {
+        let result_bytes: usize = self.result.iter().map(|path| path.len() + 24).sum();
+        let mtime_bytes: usize = self.dir_mtimes.keys().map(|dir| dir.len() + 32).sum();
+        self.root.len() + self.keyword.len() + result_bytes + mtime_bytes + 256
+    }
+
+    // false once any folder was created / removed / renamed into since the entry was stored
+    fn is_fresh(&self) -> bool {
+        self.dir_mtimes.iter().all(|(dir, mtime)| dir_mtime(Path::new(dir)) == Some(*mtime))
+    }
+}
+
+// root, keyword, options (without the fields that do not change the results)
+type CacheKey = (String, String, String);
+
+fn cache_key(root: &str, keyword: &str, options: &SearchOptions) -> CacheKey {
+    (root.to_string(), keyword.to_string(), options.cache_key())
+}
+
+struct SearchCache {
+    properties: CacheProperties,
+    entries: HashMap<CacheKey, CacheEntry>,
+    total_bytes: usize,
+    access_counter: u64,
+    dirty: bool,
+}
+
+static CACHE: Lazy<Mutex<SearchCache>> = Lazy::new(|| {
+    // write-behind : updates only touch memory, this thread persists them
+    std::thread::spawn(|| loop {
+        std::thread::sleep(CACHE_FLUSH_INTERVAL);
+        if let Err(e) = flush_cache() {
+            println!("Failed to write search cache: {}", e);
+        }
+    });
+    Mutex::new(SearchCache::load())
+});
+
+impl SearchCache {
+    fn load() -> Self {
+        let properties = fs::read_to_string(CACHE_PROPERTIES_PATH)
+            .ok()
+            .and_then(|data| serde_json::from_str::<CacheProperties>(&data).ok())
+            .unwrap_or_default();
+
+        // older cache files (or a broken one) are simply dropped
+        let stored: Vec<CacheEntry> = fs::read_to_string(CACHE_FILE_PATH)
+            .ok()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default();
+
+        let mut cache = SearchCache {
+            properties,
+            entries: HashMap::new(),
+            total_bytes: 0,
+            access_counter: 0,
+            dirty: false,
+        };
+        for entry in stored {
+            cache.access_counter = cache.access_counter.max(entry.last_access);
+            cache.insert(entry);
+        }
+        cache.evict();
+        println!("Search cache loaded: {} entries, {} bytes", cache.entries.len(), cache.total_bytes);
+        cache
+    }
+
+    fn next_access(&mut self) -> u64 {
+        self.access_counter += 1;
+        self.access_counter
+    }
+
+    fn insert(&mut self, entry: CacheEntry) {
+        let key = cache_key(&entry.root, &entry.keyword, &entry.search_options);
+        self.remove(&key);
+        self.total_bytes += entry.estimated_bytes();
+        self.entries.insert(key, entry);
+    }
+
+    fn remove(&mut self, key: &CacheKey) -> Option<CacheEntry> {
+        let entry = self.entries.remove(key)?;
+        self.total_bytes -= entry.estimated_bytes();
+        Some(entry)
+    }
+
+    fn victim(&self) -> Option<CacheKey> {
+        let entries = self.entries.iter();
+        let victim = match self.properties.policy {
+            EvictionPolicy::Lru => entries.min_by_key(|(_, entry)| entry.last_access),
+            EvictionPolicy::Lfu => entries.min_by_key(|(_, entry)| (entry.hit, entry.last_access)),
+        };
+        victim.map(|(key, _)| key.clone())
+    }
+
+    fn evict(&mut self) {
+        while self.entries.len() > self.properties.max_entries || self.total_bytes > self.properties.max_bytes {
+            match self.victim() {
+                Some(key) => {
+                    println!("Evicting cache entry: {:?}", key.1);
+                    self.remove(&key);
+                    self.dirty = true;
+                }
+                None => break,
+            }
+        }
+    }
+
+    // fresh entry for exactly this root, touched on hit; stale entries are dropped
+    fn lookup(&mut self, key: &CacheKey) -> Option<Vec<String>> {
+        let fresh = self.entries.get(key)?.is_fresh();
+        if !fresh {
+            println!("Cache entry is stale, dropping: {:?}", key.1);
+            self.remove(key);
+            self.dirty = true;
+            return None;
+        }
+        let access = self.next_access();
+        let entry = self.entries.get_mut(key)?;
+        entry.hit += 1;
+        entry.last_access = access;
+        self.dirty = true;
+        Some(entry.result.clone())
+    }
+}
+
+pub(crate) fn dir_mtime(dir: &Path) -> Option<u64> {
+    let modified = fs::metadata(dir).and_then(|meta| meta.modified()).ok()?;
+    modified.duration_since(UNIX_EPOCH).ok().map(|duration| duration.as_nanos() as u64)
+}
+
+/// cached results for `keyword` under `root`
+///
+/// An entry for the same root is used as is; otherwise an entry for a parent folder
+/// is filtered down to `root`. Entries whose folders changed since they were stored are dropped.
+pub fn find_in_cache(root: &Path, keyword: &str, options: &SearchOptions) -> Option<Vec<String>> {
+    // never stored, see SearchOptions::is_cacheable
+    if !options.is_cacheable() {
+        return None;
+    }
+    let root_str = root.to_string_lossy().to_string();
+    let mut cache = CACHE.lock().unwrap();
+
+    let key = cache_key(&root_str, keyword, options);
+    if let Some(result) = cache.lookup(&key) {
+        println!("Cache hit: {} in {}", keyword, root_str);
+        return Some(result);
+    }
+
+    let option_key = options.cache_key();
+    let parent_keys: Vec<CacheKey> = cache
+        .entries
+        .keys()
+        .filter(|(entry_root, entry_keyword, entry_options)| {
+            entry_keyword == keyword && *entry_options == option_key && root.starts_with(entry_root)
+        })
+        .cloned()
+        .collect();
+
+    for parent_key in parent_keys {
+        if let Some(result) = cache.lookup(&parent_key) {
+            println!("Cache hit from parent folder: {} in {}", keyword, parent_key.0);
+            return Some(result.into_iter().filter(|path| Path::new(path).starts_with(root)).collect());
+        }
+    }
+    None
+}
+
+// store the results of a full walk; `dir_mtimes` are the folders the walk read
+pub fn update_cache(
+    root: &Path,
+    keyword: &str,
+    new_results: impl Iterator<Item = String>,
+    current_options: &SearchOptions,
+    dir_mtimes: HashMap<String, u64>,
+) {
+    // folder mtimes can't tell when these results go stale
+    if !current_options.is_cacheable() {
+        return;
+    }
+    if dir_mtimes.len() > DIR_MTIME_LIMIT {
+        println!("Too many folders to track, not caching: {}", keyword);
+        return;
+    }
+
+    let root = root.to_string_lossy().to_string();
+    let mut cache = CACHE.lock().unwrap();
+    let key = cache_key(&root, keyword, current_options);
+    let hit = cache.entries.get(&key).map(|entry| entry.hit).unwrap_or(0);
+    let last_access = cache.next_access();
+
+    cache.insert(CacheEntry {
+        root,
+        keyword: keyword.to_string(),
+        search_options: current_options.clone(),
+        result: new_results.collect(),
+        hit: hit + 1,
+        last_access,
+        dir_mtimes,
+    });
+    cache.dirty = true;
+    cache.evict();
+}
+
+// every cached path at or under `dir`
+pub fn cached_paths_under(dir: &Path) -> Vec<String> {
+    let cache = CACHE.lock().unwrap();
+    cache
+        .entries
+        .values()
+        .flat_map(|entry| entry.result.iter())
+        .filter(|file_path| Path::new(file_path).starts_with(dir))
+        .cloned()
+        .collect()
+}
+
+// write the cache to disk if it changed since the last flush
+pub fn flush_cache() -> Result<(), String> {
+    let data = {
+        let mut cache = CACHE.lock().unwrap();
+        if !cache.dirty {
+            return Ok(());
+        }
+        cache.dirty = false;
+        let entries: Vec<&CacheEntry> = cache.entries.values().collect();
+        serde_json::to_string(&entries).map_err(|e| e.to_string())?
+    };
+
+    let tmp_path = format!("{}.tmp", CACHE_FILE_PATH);
+    let written = fs::write(&tmp_path, data).and_then(|_| fs::rename(&tmp_path, CACHE_FILE_PATH));
+    if let Err(e) = written {
+        CACHE.lock().unwrap().dirty = true;
+        return Err(e.to_string());
+    }
+    Ok(())
+}
@@ -0,0 +1,324 @@
+use chrono::Local;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
+
+use crate::search::SearchOptions;
+
+const SEARCH_RUN_DIR: &str = "../backend_properties/search_runs";
+const SEARCH_RUN_INDEX_PATH: &str = "../backend_properties/search_runs/runs_index.json";
+
+// runs kept per query (keyword + folder + options), and overall
+// runs of scheduled searches are not counted : the schedule removes them itself
+const RUNS_PER_QUERY: usize = 5;
+const RUN_LIMIT: usize = 200;
+
+// serializes index updates between concurrent searches
+static RUN_INDEX_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FileState {
+    path: String,
+    size: u64,
+    // unix timestamp (milliseconds), 0 if unknown
+    modified: u64,
+}
+
+// one stored run : the result set with size / mtime of every file
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SearchRun {
+    pub(crate) id: String,
+    keyword: String,
+    directory: String,
+    options: SearchOptions,
+    searched_at: i64,
+    files: Vec<FileState>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SearchRunSummary {
+    id: String,
+    keyword: String,
+    directory: String,
+    query_key: String,
+    // unix timestamp (seconds)
+    searched_at: i64,
+    results_count: usize,
+    // stored by a scheduled search (see scheduled_search)
+    #[serde(default)]
+    scheduled: bool,
+}
+
+// payload of the "search-diff" event and result of diff_search_runs
+#[derive(Serialize, Debug, Clone)]
+pub struct SearchDiff {
+    // set for the "search-diff" event
+    process_id: Option<String>,
+    // None when the previous results came from the cache (paths only, no "modified")
+    base_run_id: Option<String>,
+    run_id: Option<String>,
+    pub(crate) added: Vec<String>,
+    pub(crate) removed: Vec<String>,
+    pub(crate) modified: Vec<String>,
+}
+
+fn query_key(keyword: &str, directory: &str, options: &SearchOptions) -> String {
+    format!("{}\u{0}{}\u{0}{}", directory, keyword, options.cache_key())
+}
+
+fn run_file_path(id: &str) -> PathBuf {
+    Path::new(SEARCH_RUN_DIR).join(format!("{}.json", id))
+}
+
+// run ids are uuids; anything else (e.g. "../x") never names a stored run
+fn is_run_id(id: &str) -> bool {
+    uuid::Uuid::parse_str(id).is_ok()
+}
+
+fn file_state(path: &str) -> FileState {
+    let metadata = fs::metadata(path).ok();
+    let modified = metadata
+        .as_ref()
+        .and_then(|meta| meta.modified().ok())
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0);
+    FileState {
+        path: path.to_string(),
+        size: metadata.map(|meta| meta.len()).unwrap_or(0),
+        modified,
+    }
+}
+
+fn read_index() -> Vec<SearchRunSummary> {
+    fs::read_to_string(SEARCH_RUN_INDEX_PATH)
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default()
+}
+
+fn write_index(index: &[SearchRunSummary]) -> Result<(), String> {
+    let data = serde_json::to_string_pretty(index).map_err(|e| e.to_string())?;
+    fs::write(SEARCH_RUN_INDEX_PATH, data).map_err(|e| e.to_string())
+}
+
+fn read_run(id: &str) -> Result<SearchRun, String> {
+    if !is_run_id(id) {
+        return Err(format!("Invalid search run id: {}", id));
+    }
+    let data = fs::read_to_string(run_file_path(id)).map_err(|e| format!("Search run not found: {} ({})", id, e))?;
+    serde_json::from_str(&data).map_err(|e| e.to_string())
+}
+
+// most recent stored run of the same query (runs of scheduled searches are left to their schedule)
+pub fn latest_run(keyword: &str, directory: &str, options: &SearchOptions) -> Option<SearchRun> {
+    let key = query_key(keyword, directory, options);
+    let _guard = RUN_INDEX_LOCK.lock().ok()?;
+    read_index()
+        .into_iter()
+        .find(|summary| summary.query_key == key && !summary.scheduled)
+        .and_then(|summary| read_run(&summary.id).ok())
+}
+
+// a stored run by id, None if it was removed
+pub fn load_run(id: &str) -> Option<SearchRun> {
+    let _guard = RUN_INDEX_LOCK.lock().ok()?;
+    read_run(id).ok()
+}
+
+impl SearchRun {
+    // same keyword, folder and options (an edited schedule starts over)
+    pub(crate) fn is_run_of(&self, keyword: &str, directory: &str, options: &SearchOptions) -> bool {
+        query_key(&self.keyword, &self.directory, &self.options) == query_key(keyword, directory, options)
+    }
+}
+
+// the results of a finished search with size / mtime of every file, not stored
+pub fn new_run(
+    keyword: &str,
+    directory: &str,
+    options: &SearchOptions,
+    paths: impl Iterator<Item = String>,
+) -> SearchRun {
+    SearchRun {
+        id: uuid::Uuid::new_v4().to_string(),
+        keyword: keyword.to_string(),
+        directory: directory.to_string(),
+        options: options.clone(),
+        searched_at: Local::now().timestamp(),
+        files: paths.map(|path| file_state(&path)).collect(),
+    }
+}
+
+// store the results of a finished search; drops the oldest runs over the limits
+pub fn save_run(
+    keyword: &str,
+    directory: &str,
+    options: &SearchOptions,
+    paths: impl Iterator<Item = String>,
+) -> Result<SearchRun, String> {
+    store_run(new_run(keyword, directory, options, paths), false)
+}
+
+// store the results of a scheduled search : kept until remove_runs, whatever the limits
+pub fn save_scheduled_run(
+    keyword: &str,
+    directory: &str,
+    options: &SearchOptions,
+    paths: impl Iterator<Item = String>,
+) -> Result<SearchRun, String> {
+    store_run(new_run(keyword, directory, options, paths), true)
+}
+
+fn store_run(run: SearchRun, scheduled: bool) -> Result<SearchRun, String> {
+
+    let _guard = RUN_INDEX_LOCK.lock().map_err(|e| e.to_string())?;
+    fs::create_dir_all(SEARCH_RUN_DIR).map_err(|e| e.to_string())?;
+    let data = serde_json::to_string(&run).map_err(|e| e.to_string())?;
+    fs::write(run_file_path(&run.id), data).map_err(|e| e.to_string())?;
+
+    let key = query_key(&run.keyword, &run.directory, &run.options);
+    let mut index = read_index();
+    // the index is kept newest first
+    index.insert(0, SearchRunSummary {
+        id: run.id.clone(),
+        keyword: run.keyword.clone(),
+        directory: run.directory.clone(),
+        query_key: key.clone(),
+        searched_at: run.searched_at,
+        results_count: run.files.len(),
+        scheduled,
+    });
+
+    // keep RUNS_PER_QUERY per query and RUN_LIMIT overall
+    index.sort_by(|a, b| b.searched_at.cmp(&a.searched_at));
+    let mut per_query: HashMap<String, usize> = HashMap::new();
+    let mut kept = Vec::new();
+    let mut kept_unscheduled = 0;
+    for summary in index {
+        if summary.scheduled {
+            kept.push(summary);
+            continue;
+        }
+        let count = per_query.entry(summary.query_key.clone()).or_insert(0);
+        *count += 1;
+        if *count > RUNS_PER_QUERY || kept_unscheduled >= RUN_LIMIT {
+            let _ = fs::remove_file(run_file_path(&summary.id));
+        } else {
+            kept.push(summary);
+            kept_unscheduled += 1;
+        }
+    }
+    write_index(&kept)?;
+
+    println!("Search run saved: {} ({} files)", run.id, run.files.len());
+    Ok(run)
+}
+
+// drop stored runs (and their index entries)
+pub fn remove_runs(ids: &[String]) -> Result<(), String> {
+    if ids.is_empty() {
+        return Ok(());
+    }
+    let _guard = RUN_INDEX_LOCK.lock().map_err(|e| e.to_string())?;
+    for id in ids.iter().filter(|id| is_run_id(id)) {
+        let _ = fs::remove_file(run_file_path(id));
+    }
+    let mut index = read_index();
+    index.retain(|summary| !ids.contains(&summary.id));
+    write_index(&index)
+}
+
+fn diff_files(base: &[FileState], current: &[FileState]) -> (Vec<String>, Vec<String>, Vec<String>) {
+    let base_by_path: HashMap<&str, &FileState> = base.iter().map(|file| (file.path.as_str(), file)).collect();
+    let current_by_path: HashMap<&str, &FileState> = current.iter().map(|file| (file.path.as_str(), file)).collect();
+
+    let mut added = Vec::new();
+    let mut modified = Vec::new();
+    for file in current {
+        match base_by_path.get(file.path.as_str()) {
+            None => added.push(file.path.clone()),
+            Some(old) if old.size != file.size || old.modified != file.modified => modified.push(file.path.clone()),
+            Some(_) => {}
+        }
+    }
+    let removed = base
+        .iter()
+        .filter(|file| !current_by_path.contains_key(file.path.as_str()))
+        .map(|file| file.path.clone())
+        .collect();
+
+    (added, removed, modified)
+}
+
+// diff a finished search against its previous run
+pub fn diff_against_run(process_id: Option<&str>, base: &SearchRun, current: &SearchRun) -> SearchDiff {
+    let (added, removed, modified) = diff_files(&base.files, &current.files);
+    SearchDiff {
+        process_id: process_id.map(|id| id.to_string()),
+        base_run_id: Some(base.id.clone()),
+        run_id: Some(current.id.clone()),
+        added,
+        removed,
+        modified,
+    }
+}
+
+// diff against cached paths when no run was stored yet (no sizes / mtimes, so nothing is "modified")
+pub fn diff_against_paths(process_id: &str, base_paths: &[String], current: &SearchRun) -> SearchDiff {
+    let base: HashSet<&str> = base_paths.iter().map(|path| path.as_str()).collect();
+    let current_paths: HashSet<&str> = current.files.iter().map(|file| file.path.as_str()).collect();
+
+    SearchDiff {
+        process_id: Some(process_id.to_string()),
+        base_run_id: None,
+        run_id: Some(current.id.clone()),
+        added: current_paths.difference(&base).map(|path| path.to_string()).collect(),
+        removed: base.difference(&current_paths).map(|path| path.to_string()).collect(),
+        modified: vec![],
+    }
+}
+
+/// 저장된 검색 실행 목록
+///
+/// # Arguments
+///
+/// * `keyword` - 키워드 (완전 일치, 없으면 전체)
+/// * `directory` - 검색 폴더 (완전 일치, 없으면 전체)
+///
+/// # Returns
+///
+/// * `Vec<SearchRunSummary>` - 최신순
+#[tauri::command]
+pub fn list_search_runs(keyword: Option<String>, directory: Option<String>) -> Vec<SearchRunSummary> {
+    let _guard = match RUN_INDEX_LOCK.lock() {
+        Ok(guard) => guard,
+        Err(_) => return vec![],
+    };
+    read_index()
+        .into_iter()
+        .filter(|summary| keyword.as_ref().map_or(true, |keyword| &summary.keyword == keyword))
+        .filter(|summary| directory.as_ref().map_or(true, |directory| &summary.directory == directory))
+        .collect()
+}
+
+/// 저장된 두 검색 실행 비교
+///
+/// # Arguments
+///
+/// * `base_run_id` - 기준 실행 (이전)
+/// * `run_id` - 비교할 실행 (이후)
+///
+/// # Returns
+///
+/// * `Result<SearchDiff, String>` - 새로 생긴 / 사라진 / 변경된(크기, 수정 시각) 파일
+#[tauri::command]
+pub fn diff_search_runs(base_run_id: String, run_id: String) -> Result<SearchDiff, String> {
+    let base = read_run(&base_run_id)?;
+    let current = read_run(&run_id)?;
+    Ok(diff_against_run(None, &base, &current))
+}
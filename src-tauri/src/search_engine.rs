@@ -0,0 +1,787 @@
+// The search engine : directory walk and matchers. Matches go to a ResultSink, so the same
+// engine serves the app (search_files), headless callers (collect_search_results, search_cli) and tests.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::thread::ThreadId;
+use mime_guess::from_path;
+use strsim::damerau_levenshtein;
+use tokio::fs as async_fs;
+use tokio::sync::Mutex;
+
+use crate::background_io;
+use crate::byte_pattern::{self, BytePattern};
+use crate::content_index;
+use crate::hard_links;
+use crate::query_terms::{self, TermFields};
+use crate::result_sink::{CollectorSink, ResultSink};
+use crate::search::{should_filter_file_by_metadata, FileItem, SearchOptions, SearchProcess};
+use crate::search_cache;
+use crate::text_encoding;
+
+// use by fuzzy-matching
+#[derive(Deserialize, Debug)]
+struct AlgorithmConfig {
+    name: String,
+    threshold: f64,
+}
+
+// use by fuzzy-matching
+pub(crate) fn read_threshold_from_json(algorithm_name: &str) -> Result<f64, String> {
+    let data = fs::read_to_string("../backend_properties/search_properties/fuzzy_properties.json")
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+    println!("File read successfully: {:?}", data);
+
+    let configs: Vec<AlgorithmConfig> = serde_json::from_str(&data)
+        .map_err(|e| format!("Failed to parse JSON: {}", e))?;
+
+    for config in configs {
+        println!("json config name: {:?}", config.name);
+        if config.name == algorithm_name {
+            return Ok(config.threshold);
+        }
+    }
+
+    Err(format!("Algorithm not found: {}", algorithm_name))
+}
+
+
+
+// [start, end) in characters of the match target text
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct MatchRange {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl MatchRange {
+    fn from_bytes(text: &str, start: usize, end: usize) -> Self {
+        let start_chars = text[..start].chars().count();
+        MatchRange {
+            start: start_chars,
+            end: start_chars + text[start..end].chars().count(),
+        }
+    }
+
+    fn whole(text: &str) -> Vec<MatchRange> {
+        if text.is_empty() {
+            return vec![];
+        }
+        vec![MatchRange { start: 0, end: text.chars().count() }]
+    }
+}
+
+fn substring_ranges(text: &str, needle: &str) -> Vec<MatchRange> {
+    if needle.is_empty() {
+        return vec![];
+    }
+    text.match_indices(needle)
+        .map(|(start, found)| MatchRange::from_bytes(text, start, start + found.len()))
+        .collect()
+}
+
+const SEEN_NAME_LIMIT: usize = 50_000;
+
+// (device, inode) pair : identifies a folder no matter which symlink it was reached through
+pub type FileId = (u64, u64);
+
+#[cfg(unix)]
+pub fn file_identity(_path: &Path, metadata: &fs::Metadata) -> Option<FileId> {
+    use std::os::unix::fs::MetadataExt;
+    Some((metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+pub fn file_identity(path: &Path, _metadata: &fs::Metadata) -> Option<FileId> {
+    // no stable inode API here : use the canonical path instead
+    use std::hash::{Hash, Hasher};
+    let canonical = path.canonicalize().ok()?;
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    canonical.hash(&mut hasher);
+    Some((0, hasher.finish()))
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct SymlinkLoop {
+    link: String,
+    target: String,
+}
+
+// state shared by every task of a single directory walk
+pub struct WalkState {
+    root: PathBuf,
+    thread_ids: Mutex<HashSet<ThreadId>>, // For Check ThreadPool
+    visited_dirs: Mutex<HashSet<FileId>>,
+    symlink_loops: Mutex<Vec<SymlinkLoop>>,
+    // file names seen during the walk, for "did you mean" suggestions
+    seen_names: Mutex<HashSet<String>>,
+    // folder -> mtime of every folder read, used to invalidate the cache
+    dir_mtimes: Mutex<HashMap<String, u64>>,
+    // hard-linked files already reported (uniqueInodeUse)
+    reported_inodes: Mutex<HashSet<FileId>>,
+    // keyword of a byte pattern search, parsed once for the whole walk
+    byte_pattern: Option<BytePattern>,
+}
+
+impl WalkState {
+    pub fn new(root: &Path) -> Self {
+        let mut visited_dirs = HashSet::new();
+        if let Some(id) = fs::metadata(root).ok().and_then(|meta| file_identity(root, &meta)) {
+            visited_dirs.insert(id);
+        }
+        WalkState {
+            root: root.to_path_buf(),
+            thread_ids: Mutex::new(HashSet::new()),
+            visited_dirs: Mutex::new(visited_dirs),
+            symlink_loops: Mutex::new(Vec::new()),
+            seen_names: Mutex::new(HashSet::new()),
+            dir_mtimes: Mutex::new(HashMap::new()),
+            reported_inodes: Mutex::new(HashSet::new()),
+            byte_pattern: None,
+        }
+    }
+
+    // see parse_byte_pattern
+    pub fn with_byte_pattern(mut self, byte_pattern: Option<BytePattern>) -> Self {
+        self.byte_pattern = byte_pattern;
+        self
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    async fn record_thread(&self) {
+        let mut thread_ids_lock = self.thread_ids.lock().await;
+        thread_ids_lock.insert(std::thread::current().id());
+    }
+
+    // For Check ThreadPool
+    pub(crate) async fn print_thread_ids(&self) {
+        let ids_lock = self.thread_ids.lock().await;
+        println!("Unique thread IDs used: {:?}", ids_lock);
+        println!("Total number of unique threads: {}", ids_lock.len());
+    }
+
+    async fn record_symlink_loop(&self, link: &Path, target: &Path) {
+        println!("Symlink loop detected: {:?} -> {:?}", link, target);
+        let mut loops_lock = self.symlink_loops.lock().await;
+        loops_lock.push(SymlinkLoop {
+            link: link.to_string_lossy().to_string(),
+            target: target.to_string_lossy().to_string(),
+        });
+    }
+
+    async fn record_name(&self, path: &Path) {
+        if let Some(name) = path.file_stem().and_then(|name| name.to_str()) {
+            let mut names_lock = self.seen_names.lock().await;
+            if names_lock.len() < SEEN_NAME_LIMIT {
+                names_lock.insert(name.to_string());
+            }
+        }
+    }
+
+    pub async fn take_seen_names(&self) -> HashSet<String> {
+        let mut names_lock = self.seen_names.lock().await;
+        std::mem::take(&mut *names_lock)
+    }
+
+    async fn record_dir(&self, dir: &Path) {
+        let mut mtimes_lock = self.dir_mtimes.lock().await;
+        // one past the limit is enough to know the tree is too big to cache
+        if mtimes_lock.len() <= search_cache::DIR_MTIME_LIMIT {
+            if let Some(mtime) = search_cache::dir_mtime(dir) {
+                mtimes_lock.insert(dir.to_string_lossy().to_string(), mtime);
+            }
+        }
+    }
+
+    pub async fn take_dir_mtimes(&self) -> HashMap<String, u64> {
+        let mut mtimes_lock = self.dir_mtimes.lock().await;
+        std::mem::take(&mut *mtimes_lock)
+    }
+
+    // true the first time a file is reported; later links to the same data are dropped
+    async fn claim_inode(&self, path: &Path, metadata: &fs::Metadata) -> bool {
+        match file_identity(path, metadata) {
+            Some(id) => self.reported_inodes.lock().await.insert(id),
+            None => true,
+        }
+    }
+
+    pub async fn take_symlink_loops(&self) -> Vec<SymlinkLoop> {
+        let mut loops_lock = self.symlink_loops.lock().await;
+        std::mem::take(&mut *loops_lock)
+    }
+
+    // true if the folder was not walked yet, whether it was reached through a symlink or not
+    // (a symlink met before its real folder walks it first; the real folder is then skipped)
+    async fn enter_dir(&self, path: &Path, parent: &Path, metadata: &fs::Metadata, is_symlink: bool) -> bool {
+        let id = match file_identity(path, metadata) {
+            Some(id) => id,
+            None => return !is_symlink,
+        };
+
+        let mut visited_lock = self.visited_dirs.lock().await;
+        if visited_lock.insert(id) {
+            return true;
+        }
+        drop(visited_lock);
+
+        if !is_symlink {
+            println!("Skipping folder already walked through a symlink: {:?}", path);
+            return false;
+        }
+
+        // already walked : it is a loop when the target is one of our ancestors
+        let target = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        let canonical_parent = parent.canonicalize().unwrap_or_else(|_| parent.to_path_buf());
+        if canonical_parent.starts_with(&target) {
+            self.record_symlink_loop(path, &target).await;
+        } else {
+            println!("Skipping symlinked folder already walked: {:?}", path);
+        }
+        false
+    }
+}
+
+
+
+fn is_text_file(path: &Path) -> bool {
+    println!("Checking if file is text: {:?}", path);
+    if let Some(mime) = from_path(path).first() {
+        return mime.type_() == "text";
+    }
+    false
+}
+
+// content used by the content matchers : None for non-text files
+pub(crate) async fn read_text_content(path: &Path) -> Option<String> {
+    if !is_text_file(path) {
+        return None;
+    }
+    // UTF-8, or UTF-16 with a BOM
+    let bytes = async_fs::read(path).await.unwrap_or_default();
+    Some(text_encoding::decode(&bytes).map(|(text, _)| text).unwrap_or_default())
+}
+
+// content for the content search of `options` (see SearchOptions::content_any_text)
+async fn read_searched_content(path: &Path, options: &SearchOptions) -> Option<String> {
+    if !options.content_any_text {
+        return read_text_content(path).await;
+    }
+    // binaries do not decode
+    let bytes = async_fs::read(path).await.ok()?;
+    text_encoding::decode(&bytes).map(|(text, _)| text)
+}
+
+// the keyword of a byte pattern search (custom_sch_method "4") parsed up front, so a bad pattern fails
+// the search before it starts; None for the other methods
+pub fn parse_byte_pattern(keyword: &str, options: &SearchOptions) -> Result<Option<BytePattern>, String> {
+    if options.custom_sch_method != "4" {
+        return Ok(None);
+    }
+    BytePattern::parse(keyword).map(Some)
+}
+
+// the whole search under `dir` : a walk, or a ranked lookup for the content index method
+// byte pattern searches need the pattern in `walk_state` (WalkState::with_byte_pattern)
+pub async fn run_search(
+    dir: PathBuf,
+    keyword: String,
+    process: Arc<SearchProcess>,
+    options: SearchOptions,
+    sink: Arc<dyn ResultSink>,
+    walk_state: Arc<WalkState>,
+) -> Result<(), String> {
+    if options.uses_content_index() {
+        return content_index::search_with_index(&dir, &keyword, &options, sink.as_ref()).await;
+    }
+    if options.custom_sch_method == "4" && walk_state.byte_pattern.is_none() {
+        return Err("Byte pattern search started without a parsed pattern".to_string());
+    }
+    search_in_directory(dir, keyword, process, options, sink, walk_state).await
+}
+
+// a whole search with nothing but a sink : no window, cache, history or cancellation
+pub async fn search_into(dir: &Path, keyword: &str, options: &SearchOptions, sink: Arc<dyn ResultSink>) -> Result<(), String> {
+    if !dir.exists() {
+        return Err(format!("Directory does not exist: {:?}", dir));
+    }
+    let byte_pattern = parse_byte_pattern(keyword, options)?;
+    let process = Arc::new(SearchProcess::new());
+    let walk_state = Arc::new(WalkState::new(dir).with_byte_pattern(byte_pattern));
+    run_search(dir.to_path_buf(), keyword.to_string(), process, options.clone(), sink, walk_state).await
+}
+
+fn search_in_directory<'a>(
+    dir: PathBuf,
+    keyword: String,
+    process: Arc<SearchProcess>,
+    options: SearchOptions,
+    sink: Arc<dyn ResultSink>,
+    walk_state: Arc<WalkState>,
+) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
+    Box::pin(async move {
+        let mut handles = Vec::new();
+
+        // mtime before listing, so a change made during the listing invalidates the entry
+        walk_state.record_dir(&dir).await;
+
+        match async_fs::read_dir(&dir).await {
+            Ok(mut entries) => {
+                while let Ok(Some(entry)) = entries.next_entry().await {
+
+                    tokio::task::yield_now().await;
+
+                    walk_state.record_thread().await;
+
+                    let path = entry.path();
+                    let keyword = keyword.clone();
+
+                    if options.is_substring_search() {
+                        walk_state.record_name(&path).await;
+                    }
+
+                    if process.is_cancelled().await {
+                        println!("Search cancelled during directory scan.");
+                        return Ok(());
+                    }
+
+                    let is_symlink = path.is_symlink();
+                    if is_symlink && !options.custom_symbolic_chk {
+                        continue;
+                    }
+
+                    if options.is_background() {
+                        background_io::throttle(1).await;
+                    }
+
+                    let metadata = match fs::metadata(&path) {
+                        Ok(meta) => meta,
+                        Err(e) => {
+                            if e.kind() == std::io::ErrorKind::PermissionDenied {
+                                println!("Skipping file due to access error: {}", e);
+                                continue;
+                            } else if is_symlink {
+                                if e.raw_os_error() == Some(crate::cleanup_scan::SYMLINK_LOOP_OS_ERROR) {
+                                    let target = fs::read_link(&path).unwrap_or_default();
+                                    walk_state.record_symlink_loop(&path, &target).await;
+                                } else {
+                                    println!("Skipping broken symlink: {:?}", path);
+                                }
+                                continue;
+                            } else {
+                                return Err(format!("Error reading metadata: {}", e));
+                            }
+                        }
+                    };
+
+                    // report mode : the target itself is the result, never walked into
+                    let report_symlink_target = is_symlink && options.symbolic_mode == "1";
+                    let match_path = if report_symlink_target {
+                        path.canonicalize().unwrap_or_else(|_| path.clone())
+                    } else {
+                        path.clone()
+                    };
+
+                    // Metadata ( file Property chk)
+                    if options.custom_property_use {
+                        if should_filter_file_by_metadata(&path, &options) {
+                            println!("File filtered by metadata: {:?}", path);
+                            continue;
+                        }
+                    }
+
+                    if options.is_background() && options.reads_content() && metadata.is_file() {
+                        background_io::throttle(background_io::read_cost(metadata.len())).await;
+                    }
+
+                    if options.unique_inode_use && hard_links::is_hard_linked(&metadata) {
+                        // match first : the inode goes to the first link that actually matches
+                        let link_sink = CollectorSink::new();
+                        match_entry(&match_path, walk_state.root(), &keyword, walk_state.byte_pattern.as_ref(), &options, &metadata, &link_sink).await?;
+                        for file_item in link_sink.take() {
+                            if walk_state.claim_inode(&path, &metadata).await {
+                                sink.send(file_item).await?;
+                            } else {
+                                println!("Skipping hard link already reported: {:?}", path);
+                            }
+                        }
+                    } else {
+                        match_entry(&match_path, walk_state.root(), &keyword, walk_state.byte_pattern.as_ref(), &options, &metadata, sink.as_ref()).await?;
+                    }
+
+                    if metadata.is_dir() && !report_symlink_target && walk_state.enter_dir(&path, &dir, &metadata, is_symlink).await {
+                        let handle = tokio::spawn({
+                            let process = Arc::clone(&process);
+                            let sink = Arc::clone(&sink);
+                            let options_clone = options.clone();
+                            let walk_state = Arc::clone(&walk_state);
+
+                            async move {
+                                if let Err(e) = search_in_directory(path, keyword, process, options_clone, sink, walk_state).await {
+                                    if e.contains("Access is denied") || e.contains("Permission denied") {
+                                        println!("Skipping directory due to access error: {}", e);
+                                    } else {
+                                        return Err(e);
+                                    }
+                                }
+                                Ok::<_, String>(())
+                            }
+                        });
+                        handles.push(handle);
+                    }
+                }
+
+                for handle in handles {
+                    match handle.await {
+                        Ok(Ok(())) => {}
+                        Ok(Err(e)) => return Err(e),
+                        Err(e) => return Err(format!("Task failed: {:?}", e)),
+                    }
+                }
+
+                Ok(())
+            }
+            Err(e) => {
+                if e.kind() == std::io::ErrorKind::PermissionDenied {
+                    println!("Skipping directory due to access error: {}", e);
+                    Ok(())
+                } else {
+                    Err(format!("Failed to read directory: {}", e.to_string()))
+                }
+            }
+        }
+    })
+}
+
+
+// run the matcher selected by custom_sch_method; matches are sent to `sink`
+// `byte_pattern` is the keyword parsed by parse_byte_pattern
+pub(crate) async fn match_entry(
+    path: &Path,
+    root: &Path,
+    keyword: &str,
+    byte_pattern: Option<&BytePattern>,
+    options: &SearchOptions,
+    metadata: &fs::Metadata,
+    sink: &dyn ResultSink,
+) -> Result<(), String> {
+    let target = match_target_text(path, root, options);
+    match options.custom_sch_method.as_str() {
+        "1" => search_with_regex(path, &target, keyword, options, metadata, sink).await,
+        "2" => search_with_fuzzy_damerau_levenshtein(path, &target, keyword, options, metadata, sink).await,
+        "3" => search_with_fuzzy_jaccard_similarity(path, &target, keyword, options, metadata, sink).await,
+        "4" => match byte_pattern {
+            Some(pattern) => search_with_byte_pattern(path, pattern, options, metadata, sink).await,
+            None => Err("Byte pattern search started without a parsed pattern".to_string()),
+        },
+        _ => search_default(path, &target, keyword, options, metadata, sink).await,
+    }
+}
+
+// "a\b" -> "a/b", so "src/util" matches on every platform
+fn path_text(path: &Path) -> String {
+    path.to_string_lossy().replace(std::path::MAIN_SEPARATOR, "/")
+}
+
+// text the keyword is compared against, per matchTarget
+pub(crate) fn match_target_text(path: &Path, root: &Path, options: &SearchOptions) -> String {
+    match options.match_target.as_str() {
+        "1" => path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default(),
+        // symlink targets reported outside the root fall back to the absolute path
+        "2" => path.strip_prefix(root).map(path_text).unwrap_or_else(|_| path_text(path)),
+        "3" => path_text(path),
+        _ => path.file_stem().map(|name| name.to_string_lossy().to_string()).unwrap_or_default(),
+    }
+}
+
+async fn search_default(
+    path: &Path,
+    target: &str,
+    keyword: &str,
+    options: &SearchOptions,
+    metadata: &fs::Metadata,
+    sink: &dyn ResultSink,
+) -> Result<(), String> {
+    let file_name = path.file_stem().and_then(|name| name.to_str()).unwrap_or_default();
+
+    if options.search_scope == "1" && metadata.is_dir() {
+        return Ok(());
+    } else if options.search_scope == "2" && !metadata.is_dir() {
+        return Ok(());
+    }
+
+    if options.multi_term_use {
+        return search_multi_term(path, file_name, target, keyword, options, metadata, sink).await;
+    }
+
+    let matched_ranges = substring_ranges(target, keyword);
+    let is_file_name_match = !matched_ranges.is_empty();
+
+    let mut is_file_content_match = false;
+    if metadata.is_file() && options.custom_file_cont_use {
+        if let Some(content) = read_searched_content(path, options).await {
+            is_file_content_match = content.contains(keyword);
+        }
+    }
+
+    if is_file_name_match || is_file_content_match {
+        let file_item = FileItem {
+            file_name: file_name.to_string(),
+            file_path: path.to_string_lossy().to_string(),
+            matched_ranges,
+            byte_offsets: vec![],
+            score: None,
+        };
+
+        sink.send(file_item).await?;
+        println!("File or directory matched");
+    }
+
+    Ok(())
+}
+
+
+
+async fn search_multi_term(
+    path: &Path,
+    file_name: &str,
+    target: &str,
+    keyword: &str,
+    options: &SearchOptions,
+    metadata: &fs::Metadata,
+    sink: &dyn ResultSink,
+) -> Result<(), String> {
+    let terms = query_terms::parse_query(keyword);
+    let fields = TermFields::from_options(options);
+
+    let path_str = path.to_string_lossy();
+    let content = if fields.content && metadata.is_file() {
+        read_searched_content(path, options).await
+    } else {
+        None
+    };
+
+    let is_match = query_terms::matches_all(
+        &terms,
+        fields.name.then(|| target),
+        fields.path.then(|| path_str.as_ref()),
+        content.as_deref(),
+    );
+
+    if is_match {
+        let mut matched_ranges: Vec<MatchRange> = terms
+            .iter()
+            .filter(|term| !term.exclude)
+            .flat_map(|term| substring_ranges(target, &term.text))
+            .collect();
+        matched_ranges.sort_by_key(|range| range.start);
+
+        let file_item = FileItem {
+            file_name: file_name.to_string(),
+            file_path: path_str.to_string(),
+            matched_ranges,
+            byte_offsets: vec![],
+            score: None,
+        };
+
+        sink.send(file_item).await?;
+        println!("File or directory matched all terms");
+    }
+
+    Ok(())
+}
+
+// custom_sch_method "1" keyword (also the pattern of content_replace)
+pub(crate) fn compile_regex(keyword: &str) -> Result<Regex, String> {
+    Regex::new(keyword).map_err(|e| format!("Invalid regex pattern: {}", e))
+}
+
+async fn search_with_regex(
+    path: &Path,
+    target: &str,
+    keyword: &str,
+    options: &SearchOptions,
+    metadata: &fs::Metadata,
+    sink: &dyn ResultSink,
+) -> Result<(), String> {
+    let file_name = path.file_stem().and_then(|name| name.to_str()).unwrap_or_default();
+
+    if options.search_scope == "1" && metadata.is_dir() {
+        return Ok(());
+    } else if options.search_scope == "2" && !metadata.is_dir() {
+        return Ok(());
+    }
+
+    let regex = compile_regex(keyword)?;
+
+    let matched_ranges: Vec<MatchRange> = regex
+        .find_iter(target)
+        .filter(|found| !found.as_str().is_empty())
+        .map(|found| MatchRange::from_bytes(target, found.start(), found.end()))
+        .collect();
+    let is_file_name_match = regex.is_match(target);
+    let mut is_file_content_match = false;
+
+    if metadata.is_file() && options.custom_file_cont_use {
+        if let Some(content) = read_searched_content(path, options).await {
+            is_file_content_match = regex.is_match(&content);
+        }
+    }
+
+    if is_file_name_match || is_file_content_match {
+        let file_item = FileItem {
+            file_name: file_name.to_string(),
+            file_path: path.to_string_lossy().to_string(),
+            matched_ranges,
+            byte_offsets: vec![],
+            score: None,
+        };
+
+        sink.send(file_item).await?;
+        println!("File or directory matched with regex");
+    }
+
+    Ok(())
+}
+
+
+async fn search_with_fuzzy_damerau_levenshtein(
+    path: &Path,
+    target: &str,
+    keyword: &str,
+    options: &SearchOptions,
+    metadata: &fs::Metadata,
+    sink: &dyn ResultSink,
+) -> Result<(), String> {
+    println!("Performing fuzzy-based search on: {:?}", path);
+    let threshold = read_threshold_from_json("Damerau-Levenshtein").unwrap_or(2.0);
+    let file_name = path.file_stem().and_then(|name| name.to_str()).unwrap_or_default();
+    println!("Performing fuzzy-based Damerau-levenshtein threshold: {:?}", threshold);
+
+    if options.search_scope == "1" && metadata.is_dir() {
+        return Ok(());
+    } else if options.search_scope == "2" && !metadata.is_dir() {
+        return Ok(());
+    }
+    
+
+    let distance = damerau_levenshtein(target, keyword);
+    println!("distance: {:?}", distance);
+    if (distance as f64) <= threshold {
+        // fuzzy methods compare the whole target
+        let file_item = FileItem {
+            file_name: file_name.to_string(),
+            file_path: path.to_string_lossy().to_string(),
+            matched_ranges: MatchRange::whole(target),
+            byte_offsets: vec![],
+            score: None,
+        };
+
+        sink.send(file_item).await?;
+        println!("File or directory matched by fuzzy search");
+    }
+    
+    Ok(())
+}
+
+
+
+// custom_sch_method "4" : keyword is a hex byte pattern ("7F 45 4C 46", ?? = any byte), files only
+async fn search_with_byte_pattern(
+    path: &Path,
+    pattern: &BytePattern,
+    options: &SearchOptions,
+    metadata: &fs::Metadata,
+    sink: &dyn ResultSink,
+) -> Result<(), String> {
+    if !metadata.is_file() || options.search_scope == "2" {
+        return Ok(());
+    }
+
+    let byte_offsets = match byte_pattern::scan_file(path, pattern).await {
+        Ok(offsets) => offsets,
+        Err(e) => {
+            println!("Skipping file due to read error: {}", e);
+            return Ok(());
+        }
+    };
+
+    if !byte_offsets.is_empty() {
+        let file_item = FileItem {
+            file_name: path.file_stem().and_then(|name| name.to_str()).unwrap_or_default().to_string(),
+            file_path: path.to_string_lossy().to_string(),
+            matched_ranges: vec![],
+            byte_offsets,
+            score: None,
+        };
+
+        sink.send(file_item).await?;
+        println!("File matched byte pattern");
+    }
+
+    Ok(())
+}
+
+pub(crate) fn jaccard_similarity(a: &str, b: &str) -> f64 {
+    let a_grams: HashSet<_> = a.chars().collect();
+    let b_grams: HashSet<_> = b.chars().collect();
+
+    let intersection_size = a_grams.intersection(&b_grams).count();
+    let union_size = a_grams.union(&b_grams).count();
+
+    if union_size == 0 {
+        return 0.0;
+    }
+    intersection_size as f64 / union_size as f64
+}
+
+async fn search_with_fuzzy_jaccard_similarity(
+    path: &Path,
+    target: &str,
+    keyword: &str,
+    options: &SearchOptions,
+    metadata: &fs::Metadata,
+    sink: &dyn ResultSink,
+) -> Result<(), String> {
+    let file_name = path.file_stem().and_then(|name| name.to_str()).unwrap_or_default();
+    
+    if options.search_scope == "1" && metadata.is_dir() {
+        return Ok(());
+    } else if options.search_scope == "2" && !metadata.is_dir() {
+        return Ok(());
+    }
+
+    let jaccard_threshold = read_threshold_from_json("Jaccard-Similarity")
+    .map(|threshold| threshold as f64)
+    .unwrap_or(0.5);
+
+    let similarity = jaccard_similarity(target, keyword);
+    println!("Jaccard similarity between '{}' and '{}': {}", target, keyword, similarity);
+
+    if similarity >= jaccard_threshold {
+        let file_item = FileItem {
+            file_name: file_name.to_string(),
+            file_path: path.to_string_lossy().to_string(),
+            matched_ranges: MatchRange::whole(target),
+            byte_offsets: vec![],
+            score: None,
+        };
+
+        sink.send(file_item).await?;
+        println!("File or directory matched by Jaccard similarity");
+    }
+
+    Ok(())
+}
+
+
+
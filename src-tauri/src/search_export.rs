@@ -0,0 +1,225 @@
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use chrono::{DateTime, Local};
+use regex::Regex;
+use serde_json::{Map, Value};
+use strsim::damerau_levenshtein;
+use tauri::State;
+
+use crate::search::{lookup_file_owner, AppState, SearchOptions};
+use crate::search_engine::{jaccard_similarity, parse_byte_pattern, read_text_content};
+use crate::byte_pattern::{self, BytePattern};
+use crate::search_cache::find_in_cache;
+
+const EXPORT_FORMATS: [&str; 3] = ["csv", "json", "paths"];
+const EXPORT_COLUMNS: [&str; 5] = ["size", "mtime", "owner", "score", "contentHits"];
+
+struct ExportRow {
+    file_name: String,
+    file_path: String,
+    columns: Vec<(String, Value)>,
+}
+
+// match score of the file name : distance for Damerau-Levenshtein, similarity for Jaccard
+fn score_of(file_name: &str, keyword: &str, options: &SearchOptions) -> Value {
+    match options.custom_sch_method.as_str() {
+        "2" => Value::from(damerau_levenshtein(file_name, keyword)),
+        "3" => Value::from(jaccard_similarity(file_name, keyword)),
+        _ => Value::Null,
+    }
+}
+
+// `byte_pattern` : the keyword of a byte pattern search, parsed once for the whole export
+async fn content_hits_of(path: &Path, keyword: &str, byte_pattern: Option<&BytePattern>, options: &SearchOptions) -> Value {
+    // byte pattern search : number of offsets (capped like the search itself)
+    if let Some(pattern) = byte_pattern {
+        if !path.is_file() {
+            return Value::Null;
+        }
+        return byte_pattern::scan_file(path, pattern)
+            .await
+            .map(|offsets| Value::from(offsets.len()))
+            .unwrap_or(Value::Null);
+    }
+
+    if !options.custom_file_cont_use || !path.is_file() {
+        return Value::Null;
+    }
+
+    let content = match read_text_content(path).await {
+        Some(content) => content,
+        None => return Value::Null,
+    };
+
+    let hits = if options.custom_sch_method == "1" {
+        match Regex::new(keyword) {
+            Ok(regex) => regex.find_iter(&content).count(),
+            Err(_) => 0,
+        }
+    } else if keyword.is_empty() {
+        0
+    } else {
+        content.matches(keyword).count()
+    };
+
+    Value::from(hits)
+}
+
+async fn build_row(
+    file_path: &str,
+    keyword: &str,
+    byte_pattern: Option<&BytePattern>,
+    options: &SearchOptions,
+    columns: &[String],
+) -> ExportRow {
+    let path = Path::new(file_path);
+    let metadata = fs::metadata(path).ok();
+    let file_name = path.file_stem().and_then(|name| name.to_str()).unwrap_or_default().to_string();
+
+    let mut row = Vec::new();
+    for column in columns {
+        let value = match column.as_str() {
+            "size" => metadata.as_ref().map(|meta| Value::from(meta.len())).unwrap_or(Value::Null),
+            "mtime" => metadata.as_ref()
+                .and_then(|meta| meta.modified().ok())
+                .map(|modified| {
+                    let time: DateTime<Local> = modified.into();
+                    Value::from(time.format("%Y-%m-%d %H:%M:%S").to_string())
+                })
+                .unwrap_or(Value::Null),
+            "owner" => metadata.as_ref()
+                .and_then(|meta| lookup_file_owner(path, meta))
+                .map(Value::from)
+                .unwrap_or(Value::Null),
+            "score" => score_of(&file_name, keyword, options),
+            "contentHits" => content_hits_of(path, keyword, byte_pattern, options).await,
+            _ => Value::Null,
+        };
+        row.push((column.clone(), value));
+    }
+
+    ExportRow {
+        file_name,
+        file_path: file_path.to_string(),
+        columns: row,
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains(',') || value.contains('"') || value.contains('\n') || value.contains('\r') {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn value_to_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn write_csv(writer: &mut impl Write, rows: &[ExportRow], columns: &[String]) -> std::io::Result<()> {
+    let mut header = vec!["file_name".to_string(), "file_path".to_string()];
+    header.extend(columns.iter().cloned());
+    writeln!(writer, "{}", header.iter().map(|h| csv_field(h)).collect::<Vec<_>>().join(","))?;
+
+    for row in rows {
+        let mut fields = vec![csv_field(&row.file_name), csv_field(&row.file_path)];
+        fields.extend(row.columns.iter().map(|(_, value)| csv_field(&value_to_text(value))));
+        writeln!(writer, "{}", fields.join(","))?;
+    }
+    Ok(())
+}
+
+fn write_json(writer: &mut impl Write, rows: &[ExportRow]) -> Result<(), String> {
+    let items: Vec<Value> = rows.iter().map(|row| {
+        let mut object = Map::new();
+        object.insert("file_name".to_string(), Value::from(row.file_name.clone()));
+        object.insert("file_path".to_string(), Value::from(row.file_path.clone()));
+        for (column, value) in &row.columns {
+            object.insert(column.clone(), value.clone());
+        }
+        Value::Object(object)
+    }).collect();
+
+    serde_json::to_writer_pretty(writer, &items).map_err(|e| e.to_string())
+}
+
+fn write_paths(writer: &mut impl Write, rows: &[ExportRow]) -> std::io::Result<()> {
+    for row in rows {
+        writeln!(writer, "{}", row.file_path)?;
+    }
+    Ok(())
+}
+
+/// 검색 결과 내보내기
+///
+/// 끝난 검색(process_id) 또는 캐시된 검색(keyword + directory + options)의 결과를 파일로 저장
+///
+/// # Arguments
+///
+/// * `process_id` - 끝난 검색의 프로세스 ID
+/// * `keyword`, `directory`, `options` - process_id가 없을 때 캐시에서 찾을 검색
+/// * `format` - "csv" / "json" / "paths"(줄마다 경로 하나)
+/// * `columns` - "size", "mtime", "owner", "score", "contentHits" 중 선택
+/// * `output_path` - 저장할 파일 경로
+///
+/// # Returns
+///
+/// * `Result<usize, String>` - 저장한 결과 개수
+#[tauri::command]
+pub async fn export_search_results(
+    process_id: Option<String>,
+    keyword: Option<String>,
+    directory: Option<String>,
+    options: Option<SearchOptions>,
+    format: String,
+    columns: Vec<String>,
+    output_path: String,
+    state: State<'_, AppState>,
+) -> Result<usize, String> {
+    if !EXPORT_FORMATS.contains(&format.as_str()) {
+        return Err(format!("Unsupported export format: {}", format));
+    }
+    if let Some(unknown) = columns.iter().find(|c| !EXPORT_COLUMNS.contains(&c.as_str())) {
+        return Err(format!("Unknown export column: {}", unknown));
+    }
+
+    let (keyword, options, paths) = if let Some(process_id) = process_id {
+        let search = state.get_finished_search(&process_id).await
+            .ok_or_else(|| "Process not found".to_string())?;
+        let paths = search.results.paths().collect::<Vec<_>>();
+        (search.keyword, search.options, paths)
+    } else {
+        let keyword = keyword.ok_or_else(|| "Keyword is required for cached results".to_string())?;
+        let options = options.ok_or_else(|| "Options are required for cached results".to_string())?;
+        let directory = directory.ok_or_else(|| "Directory is required for cached results".to_string())?;
+        let paths = find_in_cache(Path::new(&directory), &keyword, &options)
+            .ok_or_else(|| format!("No cached results for: {}", keyword))?;
+        (keyword, options, paths)
+    };
+
+    let byte_pattern = parse_byte_pattern(&keyword, &options)?;
+    let mut rows = Vec::with_capacity(paths.len());
+    for file_path in &paths {
+        rows.push(build_row(file_path, &keyword, byte_pattern.as_ref(), &options, &columns).await);
+    }
+
+    let file = File::create(&output_path).map_err(|e| format!("Failed to create file: {}", e))?;
+    let mut writer = BufWriter::new(file);
+
+    match format.as_str() {
+        "csv" => write_csv(&mut writer, &rows, &columns).map_err(|e| format!("Failed to write to file: {}", e))?,
+        "json" => write_json(&mut writer, &rows)?,
+        _ => write_paths(&mut writer, &rows).map_err(|e| format!("Failed to write to file: {}", e))?,
+    }
+    writer.flush().map_err(|e| format!("Failed to write to file: {}", e))?;
+
+    println!("Exported {} results to {}", rows.len(), output_path);
+    Ok(rows.len())
+}
@@ -0,0 +1,423 @@
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{State, Window};
+
+use crate::search::{search_files, AppState, SearchOptions, SearchProcessInfo};
+
+const LOG_DIR_PATH: &str = "../logs";
+const HISTORY_FILE_PATH: &str = "../logs/search_history.jsonl";
+
+// retention limits : pinned entries are never dropped
+const HISTORY_MAX_ENTRIES: usize = 1000;
+const HISTORY_MAX_AGE_DAYS: i64 = 90;
+const HISTORY_MAX_FILE_BYTES: u64 = 5 * 1024 * 1024;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HistoryEntry {
+    pub id: String,
+    pub keyword: String,
+    pub directory: String,
+    pub options: SearchOptions,
+    // unix timestamp (seconds)
+    pub searched_at: i64,
+    pub duration: f64,
+    pub results_count: usize,
+    #[serde(default)]
+    pub pinned: bool,
+}
+
+// one line of the history file; pin / delete are appended instead of rewriting the file
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum HistoryRecord {
+    Add { entry: HistoryEntry },
+    Pin { id: String, pinned: bool },
+    Delete { id: String },
+}
+
+// the old one-file-per-search log format
+#[derive(Deserialize, Debug)]
+struct LegacySearchLog {
+    keyword: String,
+    options: SearchOptions,
+    directory: String,
+    duration: f64,
+    results_count: usize,
+}
+
+#[derive(Default)]
+struct HistoryStore {
+    loaded: bool,
+    entries: HashMap<String, HistoryEntry>,
+    by_time: BTreeSet<(i64, String)>,
+    by_keyword: HashMap<String, Vec<String>>,
+}
+
+static HISTORY: Lazy<Mutex<HistoryStore>> = Lazy::new(|| Mutex::new(HistoryStore::default()));
+
+impl HistoryStore {
+    fn insert(&mut self, entry: HistoryEntry) {
+        self.remove(&entry.id);
+        self.by_time.insert((entry.searched_at, entry.id.clone()));
+        self.by_keyword.entry(entry.keyword.to_lowercase()).or_default().push(entry.id.clone());
+        self.entries.insert(entry.id.clone(), entry);
+    }
+
+    fn remove(&mut self, id: &str) -> Option<HistoryEntry> {
+        let entry = self.entries.remove(id)?;
+        self.by_time.remove(&(entry.searched_at, entry.id.clone()));
+        let keyword = entry.keyword.to_lowercase();
+        if let Some(ids) = self.by_keyword.get_mut(&keyword) {
+            ids.retain(|other| other != id);
+            if ids.is_empty() {
+                self.by_keyword.remove(&keyword);
+            }
+        }
+        Some(entry)
+    }
+
+    fn apply(&mut self, record: HistoryRecord) {
+        match record {
+            HistoryRecord::Add { entry } => self.insert(entry),
+            HistoryRecord::Pin { id, pinned } => {
+                if let Some(entry) = self.entries.get_mut(&id) {
+                    entry.pinned = pinned;
+                }
+            }
+            HistoryRecord::Delete { id } => {
+                self.remove(&id);
+            }
+        }
+    }
+
+    fn ensure_loaded(&mut self) {
+        if self.loaded {
+            return;
+        }
+        self.loaded = true;
+
+        if !Path::new(HISTORY_FILE_PATH).exists() {
+            let imported = self.import_legacy_logs();
+            if let Err(e) = self.rewrite() {
+                println!("Failed to write search history: {}", e);
+                return;
+            }
+            // only once they are safely in the history file
+            for path in imported {
+                if let Err(e) = fs::remove_file(&path) {
+                    println!("Failed to remove old search log {:?}: {}", path, e);
+                }
+            }
+            return;
+        }
+
+        let file = match File::open(HISTORY_FILE_PATH) {
+            Ok(file) => file,
+            Err(e) => {
+                eprintln!("Error reading search history: {}", e);
+                return;
+            }
+        };
+
+        for line in BufReader::new(file).lines().map_while(Result::ok) {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<HistoryRecord>(&line) {
+                Ok(record) => self.apply(record),
+                Err(e) => eprintln!("Failed to parse search history line: {}", e),
+            }
+        }
+
+        self.enforce_retention();
+    }
+
+    // bring the "<timestamp>_log.json" files written by earlier versions into the store
+    // returns the imported files
+    fn import_legacy_logs(&mut self) -> Vec<PathBuf> {
+        let mut imported = Vec::new();
+        let entries = match fs::read_dir(LOG_DIR_PATH) {
+            Ok(entries) => entries,
+            Err(_) => return imported,
+        };
+
+        for file in entries.flatten() {
+            let path = file.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            let log = match fs::read_to_string(&path).ok().and_then(|data| serde_json::from_str::<LegacySearchLog>(&data).ok()) {
+                Some(log) => log,
+                None => continue,
+            };
+            let searched_at = time_from_log_name(&path).or_else(|| time_from_file(&path)).unwrap_or(0);
+
+            self.insert(HistoryEntry {
+                id: uuid::Uuid::new_v4().to_string(),
+                keyword: log.keyword,
+                directory: log.directory,
+                options: log.options,
+                searched_at,
+                duration: log.duration,
+                results_count: log.results_count,
+                pinned: false,
+            });
+            imported.push(path);
+        }
+        imported
+    }
+
+    fn append(&self, record: &HistoryRecord) -> Result<(), String> {
+        if let Some(parent) = Path::new(HISTORY_FILE_PATH).parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(HISTORY_FILE_PATH)
+            .map_err(|e| e.to_string())?;
+        let line = serde_json::to_string(record).map_err(|e| e.to_string())?;
+        writeln!(file, "{}", line).map_err(|e| e.to_string())
+    }
+
+    // compaction : one Add record per live entry
+    fn rewrite(&self) -> Result<(), String> {
+        if let Some(parent) = Path::new(HISTORY_FILE_PATH).parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let temp_path = format!("{}.tmp", HISTORY_FILE_PATH);
+        let mut file = File::create(&temp_path).map_err(|e| e.to_string())?;
+        for (_, id) in &self.by_time {
+            if let Some(entry) = self.entries.get(id) {
+                let line = serde_json::to_string(&HistoryRecord::Add { entry: entry.clone() }).map_err(|e| e.to_string())?;
+                writeln!(file, "{}", line).map_err(|e| e.to_string())?;
+            }
+        }
+        fs::rename(&temp_path, HISTORY_FILE_PATH).map_err(|e| e.to_string())
+    }
+
+    fn oldest_unpinned(&self) -> Option<String> {
+        self.by_time
+            .iter()
+            .map(|(_, id)| id)
+            .find(|id| self.entries.get(*id).map(|entry| !entry.pinned).unwrap_or(false))
+            .cloned()
+    }
+
+    fn enforce_retention(&mut self) {
+        let mut changed = false;
+
+        let min_time = Local::now().timestamp() - HISTORY_MAX_AGE_DAYS * 24 * 60 * 60;
+        let expired: Vec<String> = self.by_time
+            .iter()
+            .take_while(|(time, _)| *time < min_time)
+            .map(|(_, id)| id.clone())
+            .filter(|id| self.entries.get(id).map(|entry| !entry.pinned).unwrap_or(false))
+            .collect();
+        for id in expired {
+            self.remove(&id);
+            changed = true;
+        }
+
+        while self.entries.len() > HISTORY_MAX_ENTRIES {
+            match self.oldest_unpinned() {
+                Some(id) => {
+                    self.remove(&id);
+                    changed = true;
+                }
+                None => break,
+            }
+        }
+
+        let file_size = fs::metadata(HISTORY_FILE_PATH).map(|meta| meta.len()).unwrap_or(0);
+        if !changed && file_size <= HISTORY_MAX_FILE_BYTES {
+            return;
+        }
+
+        // appended pin / delete records also count : compact first, then trim if still too large
+        if let Err(e) = self.rewrite() {
+            println!("Failed to compact search history: {}", e);
+            return;
+        }
+        while fs::metadata(HISTORY_FILE_PATH).map(|meta| meta.len()).unwrap_or(0) > HISTORY_MAX_FILE_BYTES {
+            // drop a tenth of the entries per pass instead of rewriting once per entry
+            let drop_count = (self.entries.len() / 10).max(1);
+            let mut dropped = 0;
+            while dropped < drop_count {
+                match self.oldest_unpinned() {
+                    Some(id) => {
+                        self.remove(&id);
+                        dropped += 1;
+                    }
+                    None => break,
+                }
+            }
+            if dropped == 0 {
+                break;
+            }
+            if let Err(e) = self.rewrite() {
+                println!("Failed to compact search history: {}", e);
+                return;
+            }
+        }
+    }
+}
+
+// log names look like "2024-10-01_13-45-10_log.json"
+fn time_from_log_name(path: &Path) -> Option<i64> {
+    let name = path.file_name()?.to_str()?;
+    let timestamp = name.strip_suffix("_log.json")?;
+    let naive = NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%d_%H-%M-%S").ok()?;
+    Local.from_local_datetime(&naive).single().map(|time| time.timestamp())
+}
+
+fn time_from_file(path: &Path) -> Option<i64> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    let time: DateTime<Local> = modified.into();
+    Some(time.timestamp())
+}
+
+// save one finished search (replaces the old per-search log files)
+pub fn record_search(
+    keyword: String,
+    directory: String,
+    options: SearchOptions,
+    duration: f64,
+    results_count: usize,
+) -> Result<HistoryEntry, String> {
+    let entry = HistoryEntry {
+        id: uuid::Uuid::new_v4().to_string(),
+        keyword,
+        directory,
+        options,
+        searched_at: Local::now().timestamp(),
+        duration,
+        results_count,
+        pinned: false,
+    };
+
+    let mut store = HISTORY.lock().map_err(|e| e.to_string())?;
+    store.ensure_loaded();
+    store.append(&HistoryRecord::Add { entry: entry.clone() })?;
+    store.insert(entry.clone());
+    store.enforce_retention();
+
+    println!("Search history saved: {}", entry.id);
+    Ok(entry)
+}
+
+// every entry, oldest first
+pub fn all_entries() -> Vec<HistoryEntry> {
+    let mut store = match HISTORY.lock() {
+        Ok(store) => store,
+        Err(_) => return vec![],
+    };
+    store.ensure_loaded();
+    store.by_time
+        .iter()
+        .filter_map(|(_, id)| store.entries.get(id).cloned())
+        .collect()
+}
+
+// how often each keyword was searched
+pub fn keyword_counts() -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    for entry in all_entries() {
+        *counts.entry(entry.keyword).or_insert(0) += 1;
+    }
+    counts
+}
+
+pub fn find_entry(id: &str) -> Option<HistoryEntry> {
+    let mut store = HISTORY.lock().ok()?;
+    store.ensure_loaded();
+    store.entries.get(id).cloned()
+}
+
+/// 검색 기록 조회
+///
+/// # Arguments
+///
+/// * `keyword` - 키워드 (대소문자 무시, 완전 일치)
+/// * `from`, `to` - 검색 시각 범위 (unix timestamp, 초)
+/// * `limit` - 최대 개수
+///
+/// # Returns
+///
+/// * `Vec<HistoryEntry>` - 고정된 기록 먼저, 그 다음 최신순
+#[tauri::command]
+pub fn get_search_history(
+    keyword: Option<String>,
+    from: Option<i64>,
+    to: Option<i64>,
+    limit: Option<usize>,
+) -> Vec<HistoryEntry> {
+    let mut store = match HISTORY.lock() {
+        Ok(store) => store,
+        Err(_) => return vec![],
+    };
+    store.ensure_loaded();
+
+    let from = from.unwrap_or(i64::MIN);
+    let to = to.unwrap_or(i64::MAX);
+
+    let mut entries: Vec<HistoryEntry> = match keyword {
+        Some(keyword) => store.by_keyword
+            .get(&keyword.to_lowercase())
+            .map(|ids| ids.iter().filter_map(|id| store.entries.get(id).cloned()).collect())
+            .unwrap_or_default(),
+        None => store.by_time
+            .range((from, String::new())..)
+            .filter_map(|(_, id)| store.entries.get(id).cloned())
+            .collect(),
+    };
+
+    entries.retain(|entry| entry.searched_at >= from && entry.searched_at <= to);
+    entries.sort_by(|a, b| b.pinned.cmp(&a.pinned).then_with(|| b.searched_at.cmp(&a.searched_at)));
+    if let Some(limit) = limit {
+        entries.truncate(limit);
+    }
+    entries
+}
+
+#[tauri::command]
+pub fn pin_search_history(id: String, pinned: bool) -> Result<(), String> {
+    let mut store = HISTORY.lock().map_err(|e| e.to_string())?;
+    store.ensure_loaded();
+    if !store.entries.contains_key(&id) {
+        return Err("History entry not found".into());
+    }
+    store.append(&HistoryRecord::Pin { id: id.clone(), pinned })?;
+    store.apply(HistoryRecord::Pin { id, pinned });
+    Ok(())
+}
+
+#[tauri::command]
+pub fn delete_search_history(id: String) -> Result<(), String> {
+    let mut store = HISTORY.lock().map_err(|e| e.to_string())?;
+    store.ensure_loaded();
+    if !store.entries.contains_key(&id) {
+        return Err("History entry not found".into());
+    }
+    store.append(&HistoryRecord::Delete { id: id.clone() })?;
+    store.apply(HistoryRecord::Delete { id });
+    store.enforce_retention();
+    Ok(())
+}
+
+// run a stored search again with its keyword, folder and options
+#[tauri::command]
+pub async fn rerun_search_history(
+    window: Window,
+    id: String,
+    state: State<'_, AppState>,
+) -> Result<SearchProcessInfo, String> {
+    let entry = find_entry(&id).ok_or_else(|| "History entry not found".to_string())?;
+    search_files(window, entry.keyword, entry.directory, entry.options, state).await
+}
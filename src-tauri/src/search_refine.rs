@@ -0,0 +1,138 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Instant;
+use tauri::{State, Window};
+use tokio::sync::mpsc;
+
+use crate::search::{
+    should_filter_file_by_metadata, AppState, FinishedSearch, SearchOptions, SearchProcess, SearchProcessInfo,
+};
+use crate::result_sink::{ChannelSink, ResultSink};
+use crate::result_store::ResultStore;
+use crate::search_engine::{match_entry, parse_byte_pattern};
+use crate::search_stream::{ResultEmitter, BATCH_MAX_DELAY};
+
+// scope check for refinements without a keyword (the matchers do it otherwise)
+fn in_scope(metadata: &fs::Metadata, options: &SearchOptions) -> bool {
+    match options.search_scope.as_str() {
+        "1" => !metadata.is_dir(),
+        "2" => metadata.is_dir(),
+        _ => true,
+    }
+}
+
+/// 이전 검색 결과 안에서 다시 검색
+///
+/// 끝난 검색의 결과 목록에만 새 키워드 / 필터를 적용 (디스크를 다시 탐색하지 않음)
+/// 결과는 일반 검색과 같은 방식("search-result-batch" 또는 "search-result")으로 전송되며, 새 process id로 다시 좁힐 수 있음
+///
+/// # Arguments
+///
+/// * `process_id` - 끝난 검색의 process id
+/// * `keyword` - 추가 키워드 (없으면 필터만 적용)
+/// * `options` - 적용할 옵션 (없으면 이전 검색의 옵션)
+///
+/// # Returns
+///
+/// * `Result<SearchProcessInfo, String>` - 좁힌 검색의 process 정보
+#[tauri::command]
+pub async fn refine_search(
+    window: Window,
+    process_id: String,
+    keyword: Option<String>,
+    options: Option<SearchOptions>,
+    state: State<'_, AppState>,
+) -> Result<SearchProcessInfo, String> {
+    if keyword.is_none() && options.is_none() {
+        return Err("Nothing to refine: keyword or options required".to_string());
+    }
+
+    let previous = state.get_finished_search(&process_id).await
+        .ok_or_else(|| "Process not found".to_string())?;
+    let options = options.unwrap_or_else(|| previous.options.clone());
+    let byte_pattern = match &keyword {
+        Some(keyword) => parse_byte_pattern(keyword, &options)?,
+        None => None,
+    };
+
+    let start_time = Instant::now();
+
+    let process = Arc::new(SearchProcess::new());
+    let process_info = process.get_info().await;
+    let refined_id = process_info.id.clone();
+    state.add_process(refined_id.clone(), Arc::clone(&process)).await;
+    window.emit("process-info", process_info.clone()).expect("Failed to emit process info");
+    println!("Refining {} ({} results) as {}", process_id, previous.results.len(), refined_id);
+
+    let (tx, mut rx) = mpsc::channel(100);
+    let sink = ChannelSink::new(tx);
+
+    let previous_results = Arc::clone(&previous.results);
+    let root = PathBuf::from(&previous.directory);
+    let process_clone = Arc::clone(&process);
+    let keyword_for_spawn = keyword.clone();
+    let options_for_spawn = options.clone();
+
+    let refine = tokio::spawn(async move {
+        for item in previous_results.items() {
+            if process_clone.is_cancelled().await {
+                println!("Refine cancelled.");
+                break;
+            }
+
+            let path = Path::new(&item.file_path);
+            let metadata = match fs::metadata(path) {
+                Ok(meta) => meta,
+                Err(_) => continue,
+            };
+
+            if options_for_spawn.custom_property_use && should_filter_file_by_metadata(path, &options_for_spawn) {
+                continue;
+            }
+
+            match &keyword_for_spawn {
+                Some(keyword) => match_entry(path, &root, keyword, byte_pattern.as_ref(), &options_for_spawn, &metadata, &sink).await?,
+                None if in_scope(&metadata, &options_for_spawn) => sink.send(item).await?,
+                None => {}
+            }
+        }
+        Ok::<_, String>(())
+    });
+
+    let mut emitter = ResultEmitter::new(window.clone(), refined_id.clone(), &options, process.batch_acks());
+    let mut flush_timer = tokio::time::interval(BATCH_MAX_DELAY);
+    let mut refined = ResultStore::new(&refined_id);
+    loop {
+        tokio::select! {
+            received = rx.recv() => match received {
+                Some(file_item) => {
+                    if refined.push(file_item.clone()) {
+                        emitter.push(file_item).await;
+                    }
+                }
+                None => break,
+            },
+            _ = flush_timer.tick() => emitter.flush_if_due().await,
+        }
+    }
+    emitter.flush().await;
+    refined.finish();
+
+    let refine_result = refine.await.map_err(|e| format!("Task failed: {:?}", e))?;
+
+    process.mark_as_completed().await;
+    state.remove_process(&refined_id).await;
+    refine_result?;
+
+    // the refined set is kept under the new keyword (used for export scores)
+    let refined_keyword = keyword.unwrap_or_else(|| previous.keyword.clone());
+    state.add_finished_search(
+        refined_id,
+        FinishedSearch::new(refined_keyword, previous.directory.clone(), options, Arc::new(refined)),
+    ).await;
+
+    window.emit("search-time", start_time.elapsed().as_secs_f64()).expect("Failed to emit search time");
+
+    Ok(process.get_info().await)
+}
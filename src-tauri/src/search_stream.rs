@@ -0,0 +1,148 @@
+use serde::Serialize;
+use std::time::{Duration, Instant};
+use tauri::{State, Window};
+use tokio::sync::watch;
+
+use crate::result_sink::{ResultSink, WindowSink};
+use crate::search::{AppState, FileItem, SearchOptions};
+
+// emitMode "1" : one "search-result" event per match (the old behaviour)
+const EMIT_MODE_PER_ITEM: &str = "1";
+
+// a batch is sent when it is this big, or when its oldest item waited this long
+const BATCH_MAX_ITEMS: usize = 500;
+pub const BATCH_MAX_DELAY: Duration = Duration::from_millis(100);
+
+// batches the frontend may be behind before the search waits for an ack
+const MAX_UNACKED_BATCHES: u64 = 4;
+// a frontend that does not ack in time is treated as one that never acks
+const ACK_TIMEOUT: Duration = Duration::from_secs(2);
+
+// last batch seq acknowledged by the frontend, per search process
+pub struct BatchAcks {
+    acked: watch::Sender<u64>,
+}
+
+impl BatchAcks {
+    pub fn new() -> Self {
+        let (acked, _) = watch::channel(0);
+        BatchAcks { acked }
+    }
+
+    pub fn ack(&self, seq: u64) {
+        self.acked.send_modify(|acked| *acked = (*acked).max(seq));
+    }
+
+    fn subscribe(&self) -> watch::Receiver<u64> {
+        self.acked.subscribe()
+    }
+}
+
+// payload of the "search-result-batch" event; seq starts at 1
+#[derive(Serialize, Debug, Clone)]
+pub struct SearchResultBatch {
+    process_id: String,
+    seq: u64,
+    items: Vec<FileItem>,
+}
+
+// sends results to the frontend, batched unless emitMode is "1"
+pub struct ResultEmitter {
+    window: Window,
+    process_id: String,
+    // emitMode "1" : every match goes straight to this sink instead of the batch
+    per_item: Option<WindowSink>,
+    acks: watch::Receiver<u64>,
+    wait_for_acks: bool,
+    buffer: Vec<FileItem>,
+    oldest: Option<Instant>,
+    seq: u64,
+}
+
+impl ResultEmitter {
+    pub fn new(window: Window, process_id: String, options: &SearchOptions, acks: &BatchAcks) -> Self {
+        let per_item =
+            (options.emit_mode == EMIT_MODE_PER_ITEM).then(|| WindowSink::new(window.clone(), "search-result"));
+        ResultEmitter {
+            window,
+            process_id,
+            per_item,
+            acks: acks.subscribe(),
+            wait_for_acks: true,
+            buffer: Vec::new(),
+            oldest: None,
+            seq: 0,
+        }
+    }
+
+    pub async fn push(&mut self, file_item: FileItem) {
+        if let Some(sink) = &self.per_item {
+            if let Err(e) = sink.send(file_item).await {
+                println!("Failed to emit search result: {}", e);
+            }
+            return;
+        }
+
+        self.oldest.get_or_insert_with(Instant::now);
+        self.buffer.push(file_item);
+        if self.buffer.len() >= BATCH_MAX_ITEMS {
+            self.flush().await;
+        }
+    }
+
+    // called on a timer so a slow walk still delivers what it found
+    pub async fn flush_if_due(&mut self) {
+        if self.oldest.map_or(false, |oldest| oldest.elapsed() >= BATCH_MAX_DELAY) {
+            self.flush().await;
+        }
+    }
+
+    pub async fn flush(&mut self) {
+        if self.buffer.is_empty() {
+            return;
+        }
+        self.wait_for_ack().await;
+
+        self.seq += 1;
+        let batch = SearchResultBatch {
+            process_id: self.process_id.clone(),
+            seq: self.seq,
+            items: std::mem::take(&mut self.buffer),
+        };
+        self.oldest = None;
+        if let Err(e) = self.window.emit("search-result-batch", batch) {
+            println!("Failed to emit search result batch: {:?}", e);
+        }
+    }
+
+    // backpressure : the channel fills up and the walk pauses while we wait here
+    async fn wait_for_ack(&mut self) {
+        while self.wait_for_acks && self.seq.saturating_sub(*self.acks.borrow()) >= MAX_UNACKED_BATCHES {
+            match tokio::time::timeout(ACK_TIMEOUT, self.acks.changed()).await {
+                Ok(Ok(())) => {}
+                _ => {
+                    println!("No batch ack from frontend, sending without backpressure: {}", self.process_id);
+                    self.wait_for_acks = false;
+                }
+            }
+        }
+    }
+}
+
+/// 검색 결과 묶음 수신 확인
+///
+/// 프론트엔드가 "search-result-batch" 이벤트를 처리한 뒤 호출
+/// 확인되지 않은 묶음이 쌓이면 검색이 잠시 멈춤
+///
+/// # Arguments
+///
+/// * `process_id` - 검색 process id
+/// * `seq` - 처리한 마지막 묶음 번호
+#[tauri::command]
+pub async fn ack_search_batch(process_id: String, seq: u64, state: State<'_, AppState>) -> Result<(), String> {
+    // finished searches no longer wait for acks
+    if let Some(process) = state.get_process(&process_id).await {
+        process.batch_acks().ack(seq);
+    }
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use strsim::damerau_levenshtein;
+use tauri::{State, Window};
+
+use crate::search::{search_files, AppState, SearchProcessInfo};
+use crate::search_engine::{jaccard_similarity, read_threshold_from_json};
+use crate::search_history;
+
+const SUGGESTION_LIMIT: usize = 5;
+
+#[derive(Serialize, Debug, Clone)]
+pub struct KeywordCorrection {
+    keyword: String,
+    distance: usize,
+    similarity: f64,
+    source: String,
+}
+
+// payload of the "search-suggestions" event
+#[derive(Serialize, Debug, Clone)]
+pub struct SearchSuggestions {
+    process_id: String,
+    keyword: String,
+    directory: String,
+    suggestions: Vec<KeywordCorrection>,
+}
+
+impl SearchSuggestions {
+    pub fn new(process_id: String, keyword: String, directory: String, suggestions: Vec<KeywordCorrection>) -> Self {
+        SearchSuggestions {
+            process_id,
+            keyword,
+            directory,
+            suggestions,
+        }
+    }
+}
+
+// "invoice_2023-final" -> "invoice", "2023", "final"
+fn name_words(name: &str) -> impl Iterator<Item = &str> {
+    name.split(|c: char| !c.is_alphanumeric()).filter(|word| !word.is_empty())
+}
+
+// close spellings of `keyword` among the file names seen during the walk and the history keywords
+pub fn suggest_keywords(keyword: &str, seen_names: &HashSet<String>) -> Vec<KeywordCorrection> {
+    let max_distance = read_threshold_from_json("Damerau-Levenshtein").unwrap_or(2.0);
+    let keyword_lower = keyword.to_lowercase();
+
+    // lowercase candidate -> (candidate, source)
+    let mut candidates: HashMap<String, (String, &'static str)> = HashMap::new();
+    for name in seen_names {
+        candidates.entry(name.to_lowercase()).or_insert((name.clone(), "file"));
+        for word in name_words(name) {
+            candidates.entry(word.to_lowercase()).or_insert((word.to_string(), "file"));
+        }
+    }
+    for history_keyword in search_history::keyword_counts().into_keys() {
+        candidates.entry(history_keyword.to_lowercase()).or_insert((history_keyword, "history"));
+    }
+
+    let mut suggestions: Vec<KeywordCorrection> = candidates
+        .into_iter()
+        .filter(|(_, (candidate, _))| candidate != keyword)
+        .filter_map(|(lower, (candidate, source))| {
+            let distance = damerau_levenshtein(&keyword_lower, &lower);
+            if distance as f64 > max_distance {
+                return None;
+            }
+            Some(KeywordCorrection {
+                keyword: candidate,
+                distance,
+                similarity: jaccard_similarity(&keyword_lower, &lower),
+                source: source.to_string(),
+            })
+        })
+        .collect();
+
+    suggestions.sort_by(|a, b| {
+        a.distance
+            .cmp(&b.distance)
+            .then_with(|| b.similarity.partial_cmp(&a.similarity).unwrap_or(Ordering::Equal))
+            .then_with(|| a.keyword.cmp(&b.keyword))
+    });
+    suggestions.truncate(SUGGESTION_LIMIT);
+    suggestions
+}
+
+// re-run a finished search with one of its suggestions, keeping its folder and options
+#[tauri::command]
+pub async fn search_with_suggestion(
+    window: Window,
+    process_id: String,
+    suggestion: String,
+    state: State<'_, AppState>,
+) -> Result<SearchProcessInfo, String> {
+    let search = state.get_finished_search(&process_id).await
+        .ok_or_else(|| "Process not found".to_string())?;
+    search_files(window, suggestion, search.directory, search.options, state).await
+}
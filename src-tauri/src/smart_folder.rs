@@ -0,0 +1,269 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{State, Window};
+use tokio::sync::Mutex;
+
+use crate::content_index;
+use crate::result_sink::CollectorSink;
+use crate::search::{collect_search_results, SearchOptions, SearchProcess};
+use crate::search_cache::find_in_cache;
+
+const SMART_FOLDER_PATH: &str = "../backend_properties/smart_folder/smart_folders.json";
+const SMART_FOLDER_PROPERTIES_PATH: &str = "../backend_properties/smart_folder/smart_folder_properties.json";
+
+// smart folders are opened through list_files_in_directory with this prefix
+pub const SMART_FOLDER_SCHEME: &str = "smart://";
+
+// every watched folder re-runs its search this often
+const DEFAULT_SMART_FOLDER_REFRESH_SECS: u64 = 60;
+
+#[derive(Deserialize, Debug)]
+struct SmartFolderProperties {
+    #[serde(rename = "refreshSecs")]
+    refresh_secs: u64,
+}
+
+fn read_refresh_interval() -> Duration {
+    let secs = fs::read_to_string(SMART_FOLDER_PROPERTIES_PATH)
+        .ok()
+        .and_then(|data| serde_json::from_str::<SmartFolderProperties>(&data).ok())
+        .map(|properties| properties.refresh_secs)
+        .unwrap_or(DEFAULT_SMART_FOLDER_REFRESH_SECS);
+    Duration::from_secs(secs.max(1))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SmartFolder {
+    id: String,
+    name: String,
+    keyword: String,
+    roots: Vec<String>,
+    options: SearchOptions,
+    path: String,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct SmartFolderUpdate {
+    id: String,
+    added: Vec<String>,
+    removed: Vec<String>,
+}
+
+// running live-refresh watchers, by smart folder id
+pub struct SmartFolderState {
+    watchers: Mutex<HashMap<String, Arc<SearchProcess>>>,
+}
+
+impl SmartFolderState {
+    pub fn new() -> Self {
+        SmartFolderState {
+            watchers: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+fn read_smart_folders() -> Vec<SmartFolder> {
+    let data = fs::read_to_string(SMART_FOLDER_PATH).unwrap_or("[]".to_string());
+    serde_json::from_str(&data).unwrap_or(vec![])
+}
+
+fn save_smart_folders(folders: &Vec<SmartFolder>) -> Result<(), String> {
+    if let Some(parent) = Path::new(SMART_FOLDER_PATH).parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let data = serde_json::to_string_pretty(folders).map_err(|e| e.to_string())?;
+    fs::write(SMART_FOLDER_PATH, data).map_err(|e| e.to_string())
+}
+
+fn find_smart_folder(id: &str) -> Option<SmartFolder> {
+    read_smart_folders().into_iter().find(|folder| folder.id == id)
+}
+
+pub fn is_smart_folder_path(path: &str) -> bool {
+    path.starts_with(SMART_FOLDER_SCHEME)
+}
+
+fn smart_folder_id(path: &str) -> &str {
+    path.trim_start_matches(SMART_FOLDER_SCHEME).trim_end_matches('/')
+}
+
+pub fn smart_folder_exists(path: &str) -> bool {
+    find_smart_folder(smart_folder_id(path)).is_some()
+}
+
+async fn walk_root(root: &Path, keyword: &str, options: &SearchOptions) -> Result<Vec<String>, String> {
+    let items = collect_search_results(root.to_path_buf(), keyword.to_string(), options.clone()).await?;
+    Ok(items.into_iter().map(|item| item.file_path).collect())
+}
+
+// content index method : an index lookup when the root is indexed, otherwise a walk that
+// looks for the same terms in the file contents
+async fn search_content_index(root: &Path, folder: &SmartFolder) -> Result<Vec<String>, String> {
+    if content_index::covers_dir(root) {
+        let sink = CollectorSink::new();
+        content_index::search_with_index(root, &folder.keyword, &folder.options, &sink).await?;
+        return Ok(sink.take().into_iter().map(|item| item.file_path).collect());
+    }
+
+    println!("Smart folder root is not in the content index, walking it: {:?}", root);
+    let mut options = folder.options.clone();
+    options.custom_sch_method = "0".to_string();
+    options.multi_term_use = true;
+    options.term_fields = "content".to_string();
+    walk_root(root, &folder.keyword, &options).await
+}
+
+// re-run the saved search over every root : through the content index for its method, otherwise
+// from the cache while the root's folders did not change (see SearchOptions::is_cacheable), or a walk
+async fn run_smart_folder(folder: &SmartFolder) -> Result<Vec<String>, String> {
+    let mut sent_files = HashSet::new();
+    let mut paths = Vec::new();
+
+    for root in &folder.roots {
+        let root_path = PathBuf::from(root);
+        let root_paths = if folder.options.uses_content_index() {
+            search_content_index(&root_path, folder).await?
+        } else {
+            match find_in_cache(&root_path, &folder.keyword, &folder.options) {
+                Some(cached) => cached.into_iter().filter(|file_path| Path::new(file_path).exists()).collect(),
+                None => walk_root(&root_path, &folder.keyword, &folder.options).await?,
+            }
+        };
+        for file_path in root_paths {
+            if sent_files.insert(file_path.clone()) {
+                paths.push(file_path);
+            }
+        }
+    }
+
+    Ok(paths)
+}
+
+// contents of "smart://<id>" for list_files_in_directory
+pub async fn list_smart_folder(path: &str) -> Result<Vec<String>, String> {
+    let folder = find_smart_folder(smart_folder_id(path))
+        .ok_or_else(|| format!("Smart folder does not exist: {}", path))?;
+    run_smart_folder(&folder).await
+}
+
+#[tauri::command]
+pub fn get_smart_folders() -> Vec<SmartFolder> {
+    read_smart_folders()
+}
+
+#[tauri::command]
+pub fn create_smart_folder(
+    name: String,
+    keyword: String,
+    roots: Vec<String>,
+    options: SearchOptions,
+) -> Result<SmartFolder, String> {
+    if roots.is_empty() {
+        return Err("At least one root folder is required".to_string());
+    }
+    if let Some(missing) = roots.iter().find(|root| !Path::new(root).is_dir()) {
+        return Err(format!("Directory does not exist: {}", missing));
+    }
+
+    let id = uuid::Uuid::new_v4().to_string();
+    let folder = SmartFolder {
+        path: format!("{}{}", SMART_FOLDER_SCHEME, id),
+        id,
+        name,
+        keyword,
+        roots,
+        options,
+    };
+
+    let mut folders = read_smart_folders();
+    folders.push(folder.clone());
+    save_smart_folders(&folders)?;
+
+    Ok(folder)
+}
+
+#[tauri::command]
+pub async fn delete_smart_folder(id: String, state: State<'_, SmartFolderState>) -> Result<Vec<SmartFolder>, String> {
+    if let Some(watcher) = state.watchers.lock().await.remove(&id) {
+        watcher.cancel().await;
+    }
+
+    let mut folders = read_smart_folders();
+    folders.retain(|folder| folder.id != id);
+    save_smart_folders(&folders)?;
+
+    Ok(folders)
+}
+
+// re-run the smart folder every refreshSecs (smart_folder_properties.json, 60 by default)
+// and emit "smart-folder-update" when files start or stop matching
+#[tauri::command]
+pub async fn watch_smart_folder(
+    window: Window,
+    id: String,
+    state: State<'_, SmartFolderState>,
+) -> Result<(), String> {
+    let folder = find_smart_folder(&id).ok_or_else(|| format!("Smart folder does not exist: {}", id))?;
+
+    let process = Arc::new(SearchProcess::new());
+    if let Some(old_watcher) = state.watchers.lock().await.insert(id.clone(), Arc::clone(&process)) {
+        old_watcher.cancel().await;
+    }
+
+    tokio::spawn(async move {
+        let refresh_interval = read_refresh_interval();
+        let mut current: HashSet<String> = match run_smart_folder(&folder).await {
+            Ok(paths) => paths.into_iter().collect(),
+            Err(e) => {
+                println!("Failed to run smart folder {}: {}", folder.id, e);
+                HashSet::new()
+            }
+        };
+
+        loop {
+            tokio::time::sleep(refresh_interval).await;
+            if process.is_cancelled().await {
+                println!("Smart folder watcher stopped: {}", folder.id);
+                break;
+            }
+
+            let latest: HashSet<String> = match run_smart_folder(&folder).await {
+                Ok(paths) => paths.into_iter().collect(),
+                Err(e) => {
+                    println!("Failed to run smart folder {}: {}", folder.id, e);
+                    continue;
+                }
+            };
+
+            let update = SmartFolderUpdate {
+                id: folder.id.clone(),
+                added: latest.difference(&current).cloned().collect(),
+                removed: current.difference(&latest).cloned().collect(),
+            };
+
+            if !update.added.is_empty() || !update.removed.is_empty() {
+                if let Err(e) = window.emit("smart-folder-update", update) {
+                    println!("Failed to emit smart folder update: {:?}", e);
+                }
+            }
+            current = latest;
+        }
+    });
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn unwatch_smart_folder(id: String, state: State<'_, SmartFolderState>) -> Result<(), String> {
+    match state.watchers.lock().await.remove(&id) {
+        Some(watcher) => {
+            watcher.cancel().await;
+            Ok(())
+        }
+        None => Err("Smart folder is not watched".into()),
+    }
+}
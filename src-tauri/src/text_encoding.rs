@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+
+// how a text file was stored, so it can be written back the same way
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum TextEncoding {
+    Utf8,
+    Utf8Bom,
+    Utf16Le,
+    Utf16Be,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum LineEnding {
+    Lf,
+    CrLf,
+}
+
+const UTF8_BOM: [u8; 3] = [0xEF, 0xBB, 0xBF];
+const UTF16_LE_BOM: [u8; 2] = [0xFF, 0xFE];
+const UTF16_BE_BOM: [u8; 2] = [0xFE, 0xFF];
+
+fn decode_utf16(bytes: &[u8], to_unit: fn([u8; 2]) -> u16) -> Option<String> {
+    if bytes.len() % 2 != 0 {
+        return None;
+    }
+    let units: Vec<u16> = bytes.chunks_exact(2).map(|pair| to_unit([pair[0], pair[1]])).collect();
+    String::from_utf16(&units).ok()
+}
+
+// UTF-8 (with or without BOM) or UTF-16 with a BOM; None for anything else
+// the BOM is not part of the returned text
+pub fn decode(bytes: &[u8]) -> Option<(String, TextEncoding)> {
+    if let Some(rest) = bytes.strip_prefix(&UTF8_BOM) {
+        return String::from_utf8(rest.to_vec()).ok().map(|text| (text, TextEncoding::Utf8Bom));
+    }
+    if let Some(rest) = bytes.strip_prefix(&UTF16_LE_BOM) {
+        return decode_utf16(rest, u16::from_le_bytes).map(|text| (text, TextEncoding::Utf16Le));
+    }
+    if let Some(rest) = bytes.strip_prefix(&UTF16_BE_BOM) {
+        return decode_utf16(rest, u16::from_be_bytes).map(|text| (text, TextEncoding::Utf16Be));
+    }
+    String::from_utf8(bytes.to_vec()).ok().map(|text| (text, TextEncoding::Utf8))
+}
+
+pub fn encode(text: &str, encoding: TextEncoding) -> Vec<u8> {
+    match encoding {
+        TextEncoding::Utf8 => text.as_bytes().to_vec(),
+        TextEncoding::Utf8Bom => [&UTF8_BOM[..], text.as_bytes()].concat(),
+        TextEncoding::Utf16Le => UTF16_LE_BOM
+            .into_iter()
+            .chain(text.encode_utf16().flat_map(u16::to_le_bytes))
+            .collect(),
+        TextEncoding::Utf16Be => UTF16_BE_BOM
+            .into_iter()
+            .chain(text.encode_utf16().flat_map(u16::to_be_bytes))
+            .collect(),
+    }
+}
+
+// the ending most lines use (LF when there are no line breaks)
+pub fn line_ending(text: &str) -> LineEnding {
+    let crlf = text.matches("\r\n").count();
+    let lf = text.matches('\n').count() - crlf;
+    if crlf > lf {
+        LineEnding::CrLf
+    } else {
+        LineEnding::Lf
+    }
+}
+
+// bare "\n" in `text` -> the file's line ending
+pub fn with_line_ending(text: &str, ending: LineEnding) -> String {
+    match ending {
+        LineEnding::Lf => text.to_string(),
+        LineEnding::CrLf => {
+            let mut converted = String::with_capacity(text.len());
+            let mut previous = None;
+            for c in text.chars() {
+                if c == '\n' && previous != Some('\r') {
+                    converted.push('\r');
+                }
+                converted.push(c);
+                previous = Some(c);
+            }
+            converted
+        }
+    }
+}
@@ -0,0 +1,203 @@
+// Preview -> apply -> rollback over temporary folders; the backups go to a temporary folder too.
+
+use std::fs;
+use std::path::Path;
+
+use app_lib::content_replace::{apply_replace_in, list_replace_operations_in, preview_replace, rollback_replace_in};
+use app_lib::sch_adv_properties_slot::SearchSettings;
+use app_lib::search::SearchOptions;
+use serde_json::{json, Value};
+use tempfile::TempDir;
+
+fn options() -> SearchOptions {
+    serde_json::from_value(serde_json::to_value(SearchSettings::default()).unwrap()).unwrap()
+}
+
+fn folder(files: &[(&str, &[u8])]) -> TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    for (name, content) in files {
+        fs::write(dir.path().join(name), content).unwrap();
+    }
+    dir
+}
+
+fn utf16_le(text: &str) -> Vec<u8> {
+    [0xFF, 0xFE].into_iter().chain(text.encode_utf16().flat_map(u16::to_le_bytes)).collect()
+}
+
+async fn preview(dir: &Path, pattern: &str, replacement: &str) -> Value {
+    let directory = dir.to_string_lossy().to_string();
+    let preview = preview_replace(directory, pattern.to_string(), replacement.to_string(), options()).await.unwrap();
+    serde_json::to_value(preview).unwrap()
+}
+
+async fn apply(backups: &Path, preview: &Value) -> Value {
+    let preview_id = preview["id"].as_str().unwrap().to_string();
+    let result = apply_replace_in(backups.to_path_buf(), preview_id, None).await.unwrap();
+    serde_json::to_value(result).unwrap()
+}
+
+async fn rollback(backups: &Path, result: &Value) -> Value {
+    let operation_id = result["operation_id"].as_str().unwrap().to_string();
+    let rolled_back = rollback_replace_in(backups.to_path_buf(), operation_id).await.unwrap();
+    serde_json::to_value(rolled_back).unwrap()
+}
+
+fn is_listed(backups: &Path, operation_id: &str) -> bool {
+    list_replace_operations_in(backups)
+        .into_iter()
+        .any(|operation| serde_json::to_value(operation).unwrap()["id"] == operation_id)
+}
+
+// file names of a list of paths, or of objects with a "path"
+fn file_names(files: &Value) -> Vec<String> {
+    let mut names: Vec<String> = files
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|file| {
+            let path = file.as_str().or_else(|| file["path"].as_str()).unwrap();
+            Path::new(path).file_name().unwrap().to_string_lossy().to_string()
+        })
+        .collect();
+    names.sort();
+    names
+}
+
+#[tokio::test]
+async fn matches_on_the_same_lines_share_a_hunk() {
+    let dir = folder(&[("notes.txt", b"a1 a2\nkeep\nb1\nb2 c\n")]);
+    let preview = preview(dir.path(), r"a\d|b1\nb2|c", "X").await;
+
+    let file = &preview["files"][0];
+    assert_eq!(file["replacements"], 4);
+    assert_eq!(
+        file["hunks"],
+        json!([
+            {"line": 1, "before": "a1 a2", "after": "X X"},
+            {"line": 3, "before": "b1\nb2 c", "after": "X X"},
+        ])
+    );
+}
+
+#[tokio::test]
+async fn replacement_expands_capture_groups() {
+    let dir = folder(&[("people.txt", b"ann@example.com\nbob@test.com\n")]);
+    let backups = tempfile::tempdir().unwrap();
+    let preview = preview(dir.path(), r"(\w+)@(?P<host>\w+)\.com", "$1 at ${host} ($$)").await;
+    let result = apply(backups.path(), &preview).await;
+
+    assert_eq!(result["replacements"], 2);
+    assert_eq!(
+        fs::read_to_string(dir.path().join("people.txt")).unwrap(),
+        "ann at example ($)\nbob at test ($)\n"
+    );
+    rollback(backups.path(), &result).await;
+}
+
+#[tokio::test]
+async fn files_keep_their_encoding_and_line_endings() {
+    let dir = folder(&[
+        ("crlf.txt", b"one\r\ntwo\r\n"),
+        ("bom.txt", b"\xEF\xBB\xBFone\n"),
+        ("wide.txt", &utf16_le("\u{D0A4} one\r\n")),
+        // not text/* for the MIME check, still searched
+        ("settings.json", br#"{"one": 1}"#),
+    ]);
+    let backups = tempfile::tempdir().unwrap();
+    let preview = preview(dir.path(), "one", "1\nuno").await;
+    assert_eq!(file_names(&preview["files"]), ["bom.txt", "crlf.txt", "settings.json", "wide.txt"]);
+
+    let result = apply(backups.path(), &preview).await;
+    assert_eq!(fs::read(dir.path().join("crlf.txt")).unwrap(), b"1\r\nuno\r\ntwo\r\n");
+    assert_eq!(fs::read(dir.path().join("bom.txt")).unwrap(), b"\xEF\xBB\xBF1\nuno\n");
+    assert_eq!(fs::read(dir.path().join("wide.txt")).unwrap(), utf16_le("\u{D0A4} 1\r\nuno\r\n"));
+    assert_eq!(fs::read(dir.path().join("settings.json")).unwrap(), b"{\"1\nuno\": 1}");
+    rollback(backups.path(), &result).await;
+}
+
+#[tokio::test]
+async fn files_changed_since_the_preview_are_skipped() {
+    let dir = folder(&[("kept.txt", b"old value\n"), ("edited.txt", b"old value\n")]);
+    let backups = tempfile::tempdir().unwrap();
+    let preview = preview(dir.path(), "old", "new").await;
+    fs::write(dir.path().join("edited.txt"), b"old value, edited\n").unwrap();
+
+    let result = apply(backups.path(), &preview).await;
+    assert_eq!(file_names(&result["replaced"]), ["kept.txt"]);
+    assert_eq!(file_names(&result["skipped"]), ["edited.txt"]);
+    assert_eq!(result["skipped"][0]["reason"], "File changed since the preview");
+    assert_eq!(fs::read_to_string(dir.path().join("edited.txt")).unwrap(), "old value, edited\n");
+
+    // a preview is applied once
+    let preview_id = preview["id"].as_str().unwrap().to_string();
+    assert!(apply_replace_in(backups.path().to_path_buf(), preview_id, None).await.is_err());
+    rollback(backups.path(), &result).await;
+}
+
+#[tokio::test]
+async fn rollback_restores_the_original_bytes() {
+    let originals: [(&str, Vec<u8>); 3] = [
+        ("crlf.txt", b"alpha\r\nbeta\r\n".to_vec()),
+        ("bom.txt", b"\xEF\xBB\xBFalpha\n".to_vec()),
+        ("wide.txt", utf16_le("alpha\r\n")),
+    ];
+    let dir = folder(&originals.iter().map(|(name, bytes)| (*name, bytes.as_slice())).collect::<Vec<_>>());
+    let backups = tempfile::tempdir().unwrap();
+
+    let result = apply(backups.path(), &preview(dir.path(), "alpha", "gamma\ndelta").await).await;
+    assert_eq!(file_names(&result["replaced"]), ["bom.txt", "crlf.txt", "wide.txt"]);
+    let operation_id = result["operation_id"].as_str().unwrap().to_string();
+    assert!(is_listed(backups.path(), &operation_id));
+
+    let rolled_back = rollback(backups.path(), &result).await;
+    assert_eq!(file_names(&rolled_back["restored"]), ["bom.txt", "crlf.txt", "wide.txt"]);
+    for (name, bytes) in &originals {
+        assert_eq!(&fs::read(dir.path().join(name)).unwrap(), bytes, "{}", name);
+    }
+    assert!(!is_listed(backups.path(), &operation_id));
+}
+
+#[tokio::test]
+async fn rollback_rejects_ids_that_are_not_operations() {
+    let backups = tempfile::tempdir().unwrap();
+    for id in ["..", "../replace_backups", ""] {
+        assert!(rollback_replace_in(backups.path().to_path_buf(), id.to_string()).await.is_err(), "{}", id);
+    }
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn replaced_files_keep_their_owner_attributes_and_permissions() {
+    use std::os::unix::fs::{MetadataExt, PermissionsExt};
+
+    let dir = folder(&[("shared.txt", b"draft\n")]);
+    let backups = tempfile::tempdir().unwrap();
+    let path = dir.path().join("shared.txt");
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o640)).unwrap();
+    // tmpfs and some other file systems have no user attributes
+    let tagged = xattr::set(&path, "user.tag", b"keep").is_ok();
+    // handing a file to another user needs root
+    let c_path = std::ffi::CString::new(path.to_string_lossy().as_bytes()).unwrap();
+    let chowned = unsafe { libc::chown(c_path.as_ptr(), 65534, 65534) } == 0;
+    let before = fs::metadata(&path).unwrap();
+
+    let result = apply(backups.path(), &preview(dir.path(), "draft", "final").await).await;
+    assert_eq!(file_names(&result["replaced"]), ["shared.txt"]);
+
+    let check = || {
+        let after = fs::metadata(&path).unwrap();
+        assert_eq!(after.mode() & 0o7777, 0o640);
+        if chowned {
+            assert_eq!((after.uid(), after.gid()), (before.uid(), before.gid()));
+        }
+        if tagged {
+            assert_eq!(xattr::get(&path, "user.tag").unwrap(), Some(b"keep".to_vec()));
+        }
+    };
+    check();
+    // rollback writes the same way
+    rollback(backups.path(), &result).await;
+    assert_eq!(fs::read_to_string(&path).unwrap(), "draft\n");
+    check();
+}
@@ -0,0 +1,290 @@
+// Real searches over temporary folders, through the result sinks (no window needed).
+// Run from src-tauri like the app ("cargo test"), so the fuzzy thresholds in
+// ../backend_properties are found.
+
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use app_lib::result_sink::{ChannelSink, CollectorSink, FileSink};
+use app_lib::sch_adv_properties_slot::SearchSettings;
+use app_lib::search::{FileItem, SearchOptions};
+use app_lib::search_engine::search_into;
+use serde_json::{json, Value};
+use tempfile::TempDir;
+use tokio::sync::mpsc;
+
+// default settings with some fields overridden, e.g. json!({"searchScope": "1"})
+fn options(overrides: Value) -> SearchOptions {
+    let mut options = serde_json::to_value(SearchSettings::default()).unwrap();
+    for (key, value) in overrides.as_object().unwrap() {
+        options[key] = value.clone();
+    }
+    serde_json::from_value(options).unwrap()
+}
+
+//  root/
+//    notes/report.txt       "quarterly numbers"
+//    notes/Report-old.md    "draft"
+//    notes/archive.txt      "old numbers"
+//    images/logo.png        png header
+//    reports/               (folder)
+//    readme.txt             "see the report folder"
+fn fixture() -> TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    fs::create_dir_all(root.join("notes")).unwrap();
+    fs::create_dir_all(root.join("images")).unwrap();
+    fs::create_dir_all(root.join("reports")).unwrap();
+    fs::write(root.join("notes/report.txt"), "quarterly numbers").unwrap();
+    fs::write(root.join("notes/Report-old.md"), "draft").unwrap();
+    fs::write(root.join("notes/archive.txt"), "old numbers").unwrap();
+    fs::write(root.join("images/logo.png"), [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 0x00]).unwrap();
+    fs::write(root.join("readme.txt"), "see the report folder").unwrap();
+    dir
+}
+
+async fn search(root: &Path, keyword: &str, options: SearchOptions) -> Vec<FileItem> {
+    let sink = Arc::new(CollectorSink::new());
+    search_into(root, keyword, &options, sink.clone()).await.unwrap();
+    sink.take()
+}
+
+// paths relative to the root, sorted ("notes/report.txt")
+fn relative_paths(root: &Path, items: &[FileItem]) -> Vec<String> {
+    let mut paths: Vec<String> = items
+        .iter()
+        .map(|item| {
+            Path::new(&item.file_path)
+                .strip_prefix(root)
+                .unwrap()
+                .to_string_lossy()
+                .replace(std::path::MAIN_SEPARATOR, "/")
+        })
+        .collect();
+    paths.sort();
+    paths
+}
+
+#[tokio::test]
+async fn substring_search_matches_names() {
+    let dir = fixture();
+    let items = search(dir.path(), "report", options(json!({}))).await;
+    assert_eq!(relative_paths(dir.path(), &items), ["notes/report.txt", "reports"]);
+
+    let report = items.iter().find(|item| item.file_name == "report").unwrap();
+    assert_eq!((report.matched_ranges[0].start, report.matched_ranges[0].end), (0, 6));
+}
+
+#[tokio::test]
+async fn search_scope_limits_files_and_folders() {
+    let dir = fixture();
+    let files = search(dir.path(), "report", options(json!({"searchScope": "1"}))).await;
+    assert_eq!(relative_paths(dir.path(), &files), ["notes/report.txt"]);
+
+    let folders = search(dir.path(), "report", options(json!({"searchScope": "2"}))).await;
+    assert_eq!(relative_paths(dir.path(), &folders), ["reports"]);
+}
+
+#[tokio::test]
+async fn content_search_reads_text_files() {
+    let dir = fixture();
+    let items = search(dir.path(), "quarterly", options(json!({"customFileContUse": true}))).await;
+    assert_eq!(relative_paths(dir.path(), &items), ["notes/report.txt"]);
+}
+
+#[tokio::test]
+async fn regex_search() {
+    let dir = fixture();
+    let items = search(dir.path(), "(?i)^report-", options(json!({"customSchMethod": "1"}))).await;
+    assert_eq!(relative_paths(dir.path(), &items), ["notes/Report-old.md"]);
+}
+
+#[tokio::test]
+async fn fuzzy_searches_use_the_thresholds() {
+    let dir = fixture();
+    // "reprot" is one transposition away from "report"
+    let damerau = search(dir.path(), "reprot", options(json!({"customSchMethod": "2", "searchScope": "1"}))).await;
+    assert_eq!(relative_paths(dir.path(), &damerau), ["notes/report.txt"]);
+
+    let jaccard = search(dir.path(), "readme", options(json!({"customSchMethod": "3", "searchScope": "1"}))).await;
+    assert_eq!(relative_paths(dir.path(), &jaccard), ["readme.txt"]);
+}
+
+#[tokio::test]
+async fn multi_term_search_needs_every_term() {
+    let dir = fixture();
+    let fields = json!({"multiTermUse": true, "searchScope": "1", "termFields": "content"});
+    let all = search(dir.path(), "numbers", options(fields.clone())).await;
+    assert_eq!(relative_paths(dir.path(), &all), ["notes/archive.txt", "notes/report.txt"]);
+
+    // only "-old" rules out notes/archive.txt
+    let items = search(dir.path(), "numbers -old", options(fields)).await;
+    assert_eq!(relative_paths(dir.path(), &items), ["notes/report.txt"]);
+}
+
+#[tokio::test]
+async fn multi_term_search_keeps_quoted_phrases() {
+    let dir = fixture();
+    let fields = json!({"multiTermUse": true, "searchScope": "1", "termFields": "content"});
+
+    let phrase = search(dir.path(), "\"old numbers\"", options(fields.clone())).await;
+    assert_eq!(relative_paths(dir.path(), &phrase), ["notes/archive.txt"]);
+
+    // both words are there, but not as this phrase
+    let reordered = search(dir.path(), "\"numbers old\"", options(fields)).await;
+    assert!(reordered.is_empty());
+}
+
+#[tokio::test]
+async fn byte_pattern_search_reports_offsets() {
+    let dir = fixture();
+    let items = search(dir.path(), "50 4E 47 ?? 0A", options(json!({"customSchMethod": "4"}))).await;
+    assert_eq!(relative_paths(dir.path(), &items), ["images/logo.png"]);
+    assert_eq!(items[0].byte_offsets, [1]);
+}
+
+#[tokio::test]
+async fn invalid_byte_pattern_fails_before_the_walk() {
+    // no file to scan : the pattern is still checked
+    let dir = tempfile::tempdir().unwrap();
+    let sink = Arc::new(CollectorSink::new());
+    let result = search_into(dir.path(), "50 4G", &options(json!({"customSchMethod": "4"})), sink).await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn match_target_relative_path() {
+    let dir = fixture();
+    let items = search(dir.path(), "notes/r", options(json!({"matchTarget": "2"}))).await;
+    assert_eq!(relative_paths(dir.path(), &items), ["notes/report.txt"]);
+}
+
+#[tokio::test]
+async fn missing_folder_is_an_error() {
+    let dir = fixture();
+    let sink = Arc::new(CollectorSink::new());
+    let result = search_into(&dir.path().join("nowhere"), "report", &options(json!({})), sink).await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn channel_sink_delivers_every_match() {
+    let dir = fixture();
+    let (tx, mut rx) = mpsc::channel(1);
+    let walk = {
+        let root = dir.path().to_path_buf();
+        tokio::spawn(async move { search_into(&root, "re", &options(json!({})), Arc::new(ChannelSink::new(tx))).await })
+    };
+
+    let mut items = Vec::new();
+    while let Some(item) = rx.recv().await {
+        items.push(item);
+    }
+    walk.await.unwrap().unwrap();
+    assert_eq!(relative_paths(dir.path(), &items), ["notes/report.txt", "readme.txt", "reports"]);
+}
+
+#[tokio::test]
+async fn file_sink_writes_json_lines() {
+    let dir = fixture();
+    let out = tempfile::tempdir().unwrap();
+    let out_path = out.path().join("results/found.jsonl");
+    {
+        let sink = Arc::new(FileSink::create(&out_path).unwrap());
+        search_into(dir.path(), "logo", &options(json!({})), sink).await.unwrap();
+    }
+
+    let lines: Vec<FileItem> = fs::read_to_string(&out_path)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(relative_paths(dir.path(), &lines), ["images/logo.png"]);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn symlink_loops_are_walked_once() {
+    let dir = fixture();
+    std::os::unix::fs::symlink(dir.path().join("notes"), dir.path().join("notes/again")).unwrap();
+
+    let items = search(dir.path(), "report", options(json!({"customSymbolicChk": true, "searchScope": "1"}))).await;
+    assert_eq!(relative_paths(dir.path(), &items), ["notes/report.txt"]);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn folders_reached_through_a_symlink_are_walked_once() {
+    let dir = fixture();
+    fs::create_dir_all(dir.path().join("archive/2023")).unwrap();
+    fs::write(dir.path().join("archive/2023/budget.txt"), "").unwrap();
+    // the link sits closer to the root than the folder it points to
+    std::os::unix::fs::symlink(dir.path().join("archive/2023"), dir.path().join("last-year")).unwrap();
+
+    let items = search(dir.path(), "budget", options(json!({"customSymbolicChk": true, "searchScope": "1"}))).await;
+    assert_eq!(items.len(), 1, "{:?}", relative_paths(dir.path(), &items));
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn unique_inode_reports_hard_links_once() {
+    let dir = fixture();
+    fs::hard_link(dir.path().join("notes/report.txt"), dir.path().join("report-link.txt")).unwrap();
+
+    let all = search(dir.path(), "report", options(json!({"searchScope": "1"}))).await;
+    assert_eq!(relative_paths(dir.path(), &all), ["notes/report.txt", "report-link.txt"]);
+
+    // either name, but only one of them
+    let unique = search(dir.path(), "report", options(json!({"searchScope": "1", "uniqueInodeUse": true}))).await;
+    assert_eq!(unique.len(), 1);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn xattr_filter_still_walks_untagged_folders() {
+    let dir = fixture();
+    // tmpfs and some other file systems have no user attributes
+    if xattr::set(dir.path().join("notes/report.txt"), "user.tag", b"keep").is_err() {
+        return;
+    }
+
+    let items = search(
+        dir.path(),
+        "report",
+        options(json!({"customPropertyUse": true, "customXattrUse": true, "xattrName": "user.tag"})),
+    )
+    .await;
+    assert_eq!(relative_paths(dir.path(), &items), ["notes/report.txt", "reports"]);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn owner_filter_compares_user_names() {
+    // the owner filter needs root on unix
+    let whoami = match std::process::Command::new("id").arg("-un").output() {
+        Ok(whoami) => whoami,
+        Err(_) => return,
+    };
+    let user = String::from_utf8_lossy(&whoami.stdout).trim().to_string();
+    if user != "root" {
+        return;
+    }
+    let dir = fixture();
+
+    let owned = search(
+        dir.path(),
+        "report",
+        options(json!({"searchScope": "1", "customPropertyUse": true, "customFileOwnerUse": true, "ownerName": user})),
+    )
+    .await;
+    assert_eq!(relative_paths(dir.path(), &owned), ["notes/report.txt"]);
+
+    let others = search(
+        dir.path(),
+        "report",
+        options(json!({"searchScope": "1", "customPropertyUse": true, "customFileOwnerUse": true, "ownerName": "nobody-else"})),
+    )
+    .await;
+    assert!(others.is_empty());
+}